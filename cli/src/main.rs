@@ -0,0 +1,780 @@
+//! Command-line tool for running contests of the nft_contest program against an RPC node,
+//! e.g. a local test validator.
+
+mod spec;
+
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand};
+use nft_contest::state::{Artwork, Contest, Counter, OwnerProfile, VoteData, VoterRewardMode};
+use nft_contest_client::rpc::RpcClient;
+use nft_contest_client::simulate::{self, Simulation};
+use nft_contest_client::{accounts, filters, instructions, pda};
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+use spec::{ContestSpec, TalliesSpec};
+use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::instruction::create_associated_token_account;
+use std::path::PathBuf;
+
+// artworks and vote data passed as remaining accounts per transaction, within the size limit
+const TALLY_BATCH_SIZE: usize = 20;
+const DISTRIBUTE_BATCH_SIZE: usize = 10;
+
+#[derive(Parser)]
+#[clap(name = "nft-contest", version, about)]
+struct Cli {
+    /// JSON RPC URL of the cluster
+    #[clap(long, short = 'u', default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Keypair signing and paying for transactions, defaults to the Solana CLI keypair
+    #[clap(long, short = 'k')]
+    keypair: Option<PathBuf>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the global contest counter, once per deployment
+    Init,
+    /// Launch a contest described by a TOML or JSON spec
+    Launch { spec: PathBuf },
+    /// Cancel a contest without artworks before voting starts, refunding its prize
+    Cancel { contest: Pubkey },
+    /// Submit an NFT held by the keypair to a contest
+    Submit { contest: Pubkey, nft_mint: Pubkey },
+    /// Replace the curators allowed to review artworks, as contest owner
+    SetCurators {
+        contest: Pubkey,
+        curators: Vec<Pubkey>,
+    },
+    /// Approve an artwork for voting, as contest owner or curator
+    Approve { contest: Pubkey, artwork: Pubkey },
+    /// Reject an artwork and return its NFT to the artist, as contest owner or curator
+    Reject { contest: Pubkey, artwork: Pubkey },
+    /// Vote for an artwork, locking the keypair's prize tokens in vote_weighted contests
+    Vote { contest: Pubkey, artwork: Pubkey },
+    /// Take back the prize tokens locked by the keypair's vote, once voting has ended
+    Unlock { contest: Pubkey },
+    /// Tally the votes of every artwork once voting has ended, over as many transactions as needed
+    Tally { contest: Pubkey },
+    /// Record the winners and payouts of a tallied contest
+    Finalize { contest: Pubkey },
+    /// Pay the rewards of every voter who has not claimed yet to their associated token account
+    Distribute { contest: Pubkey },
+    /// Claim a prize, or the submitted NFT as contest owner
+    Claim {
+        #[clap(subcommand)]
+        claim: Claim,
+    },
+    /// Send what is left in the prize vault after the claim deadline to the keypair, as contest
+    /// owner
+    Sweep { contest: Pubkey },
+    /// Print a contest with the tallies of its artworks
+    Status { contest: Pubkey },
+    /// Upgrade a counter, contest, artwork or vote data created before accounts were versioned,
+    /// as upgrade authority of the program. Artworks must be migrated before their contest
+    Migrate {
+        account: Pubkey,
+        /// Contest of an artwork or vote data, which the legacy layouts do not record
+        #[clap(long)]
+        contest: Option<Pubkey>,
+    },
+    /// Print the winners and payouts of a contest spec with hypothetical tallies, offline
+    Simulate {
+        spec: PathBuf,
+        tallies: PathBuf,
+        /// Weight of the vote whose reward is printed, the voter token balance in vote_weighted
+        /// contests, at most the total vote weight of the tallies
+        #[clap(long, default_value_t = 1)]
+        weight: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum Claim {
+    /// Claim the artist prize of a winning entry
+    Artist {
+        contest: Pubkey,
+        #[clap(long, default_value_t = 0)]
+        entry_index: u8,
+    },
+    /// Claim the voter reward of the keypair's vote
+    Voter { contest: Pubkey },
+    /// Claim the NFT of an artwork as contest owner
+    Owner {
+        contest: Pubkey,
+        artist: Pubkey,
+        #[clap(long, default_value_t = 0)]
+        entry_index: u8,
+    },
+}
+
+struct Runner {
+    rpc: RpcClient,
+    payer: Keypair,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    // simulations don't need a keypair nor a cluster
+    if let Command::Simulate {
+        spec,
+        tallies,
+        weight,
+    } = &cli.command
+    {
+        return simulate(
+            &ContestSpec::from_file(spec)?,
+            &TalliesSpec::from_file(tallies)?,
+            *weight,
+        );
+    }
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => default_keypair_path()?,
+    };
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow!("cannot read keypair {}: {}", keypair_path.display(), err))?;
+    let runner = Runner {
+        rpc: RpcClient::new(&cli.url),
+        payer,
+    };
+
+    match cli.command {
+        Command::Init => runner.init(),
+        Command::Launch { spec } => runner.launch(&ContestSpec::from_file(&spec)?),
+        Command::Cancel { contest } => runner.cancel(&contest),
+        Command::Submit { contest, nft_mint } => runner.submit(&contest, &nft_mint),
+        Command::SetCurators { contest, curators } => runner.set_curators(&contest, curators),
+        Command::Approve { contest, artwork } => runner.approve(&contest, &artwork),
+        Command::Reject { contest, artwork } => runner.reject(&contest, &artwork),
+        Command::Vote { contest, artwork } => runner.vote(&contest, &artwork),
+        Command::Unlock { contest } => runner.unlock(&contest),
+        Command::Tally { contest } => runner.tally(&contest),
+        Command::Finalize { contest } => runner.finalize(&contest),
+        Command::Distribute { contest } => runner.distribute(&contest),
+        Command::Claim { claim } => match claim {
+            Claim::Artist {
+                contest,
+                entry_index,
+            } => runner.claim_by_artist(&contest, entry_index),
+            Claim::Voter { contest } => runner.claim_by_voter(&contest),
+            Claim::Owner {
+                contest,
+                artist,
+                entry_index,
+            } => runner.claim_by_contest_owner(&contest, &artist, entry_index),
+        },
+        Command::Sweep { contest } => runner.sweep(&contest),
+        Command::Status { contest } => runner.status(&contest),
+        Command::Migrate { account, contest } => runner.migrate(&account, contest.as_ref()),
+        Command::Simulate { .. } => unreachable!("simulations run without a runner"),
+    }
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").map_err(|_| anyhow!("HOME is not set, pass --keypair"))?;
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
+
+impl Runner {
+    fn send(&self, instructions: &[Instruction]) -> Result<()> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            self.rpc.get_latest_blockhash()?,
+        );
+        let signature = self.rpc.send_and_confirm(&transaction)?;
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+
+    fn fetch<T: anchor_lang::AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)?
+            .ok_or_else(|| anyhow!("account {} not found", address))?;
+        Ok(accounts::decode(&data)?)
+    }
+
+    /// Unix timestamp of the cluster clock, which the program compares the contest schedule to.
+    fn now(&self) -> Result<u64> {
+        let data = self
+            .rpc
+            .get_account_data(&sysvar::clock::ID)?
+            .ok_or_else(|| anyhow!("clock sysvar not found"))?;
+        let clock: Clock = bincode::deserialize(&data)?;
+        Ok(clock.unix_timestamp as u64)
+    }
+
+    /// Associated token account of the keypair for `mint`, with the instruction creating it when
+    /// it doesn't exist yet.
+    fn token_account(&self, mint: &Pubkey, setup: &mut Vec<Instruction>) -> Result<Pubkey> {
+        let owner = self.payer.pubkey();
+        let address = get_associated_token_address(&owner, mint);
+        if self.rpc.get_account_data(&address)?.is_none() {
+            setup.push(create_associated_token_account(&owner, &owner, mint));
+        }
+        Ok(address)
+    }
+
+    fn artworks(&self, contest: &Contest) -> Result<Vec<(Pubkey, Artwork)>> {
+        let mut artworks = self
+            .rpc
+            .get_program_accounts(
+                &nft_contest::ID,
+                &filters::artworks_by_contest(contest.contest_id),
+            )?
+            .into_iter()
+            .map(|(address, data)| Ok((address, accounts::decode_artwork(&data)?)))
+            .collect::<Result<Vec<_>>>()?;
+        artworks.sort_by_key(|(_, artwork)| artwork.artwork_id);
+        Ok(artworks)
+    }
+
+    /// Address of the artwork `artist` submitted as `entry_index`. In a contest migrated from the
+    /// first release, the one artwork an artist could submit then keeps its legacy address.
+    fn artwork_address(
+        &self,
+        contest_address: &Pubkey,
+        contest: &Contest,
+        artist: &Pubkey,
+        entry_index: u8,
+    ) -> Result<Pubkey> {
+        if contest.legacy_seeds && entry_index == 0 {
+            let legacy_address = pda::find_legacy_artwork_address(contest_address, artist).0;
+            if self.rpc.get_account_data(&legacy_address)?.is_some() {
+                return Ok(legacy_address);
+            }
+        }
+        Ok(pda::find_artwork_address(contest_address, artist, entry_index).0)
+    }
+
+    fn init(&self) -> Result<()> {
+        self.send(&[instructions::initialize(&self.payer.pubkey())])?;
+        println!("Counter: {}", pda::find_counter_address().0);
+        Ok(())
+    }
+
+    fn launch(&self, spec: &ContestSpec) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let counter: Counter = self.fetch(&pda::find_counter_address().0)?;
+        let owner_profile_address = pda::find_owner_profile_address(&owner).0;
+        let owner_contest_count = match self.rpc.get_account_data(&owner_profile_address)? {
+            Some(data) => accounts::decode::<OwnerProfile>(&data)?.contest_count,
+            None => 0,
+        };
+        let prize_mint = spec.prize_mint()?;
+        self.send(&[instructions::launch(
+            &owner,
+            counter.contest_count,
+            owner_contest_count,
+            &prize_mint,
+            &get_associated_token_address(&owner, &prize_mint),
+            spec.to_launch_args()?,
+        )])?;
+        println!(
+            "Contest: {}",
+            pda::find_contest_address(&owner, counter.contest_count).0
+        );
+        Ok(())
+    }
+
+    fn cancel(&self, contest_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let mut ixs = vec![];
+        let owner_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(with_legacy_seeds(
+            instructions::cancel_contest(
+                &self.payer.pubkey(),
+                contest_address,
+                &contest.prize_mint,
+                &owner_token_account,
+            ),
+            &contest,
+            &[],
+        ));
+        self.send(&ixs)
+    }
+
+    fn submit(&self, contest: &Pubkey, nft_mint: &Pubkey) -> Result<()> {
+        let artist = self.payer.pubkey();
+        let entry_index = match self
+            .rpc
+            .get_account_data(&pda::find_artist_entries_address(contest, &artist).0)?
+        {
+            Some(data) => accounts::decode::<nft_contest::state::ArtistEntries>(&data)?.entry_count,
+            None => 0,
+        };
+        self.send(&[instructions::submit(
+            &artist,
+            contest,
+            entry_index,
+            nft_mint,
+            &get_associated_token_address(&artist, nft_mint),
+        )])?;
+        println!(
+            "Artwork: {}",
+            pda::find_artwork_address(contest, &artist, entry_index).0
+        );
+        Ok(())
+    }
+
+    fn set_curators(&self, contest: &Pubkey, curators: Vec<Pubkey>) -> Result<()> {
+        self.send(&[instructions::set_curators(
+            &self.payer.pubkey(),
+            contest,
+            curators,
+        )])
+    }
+
+    fn approve(&self, contest: &Pubkey, artwork: &Pubkey) -> Result<()> {
+        self.send(&[instructions::approve_artwork(
+            &self.payer.pubkey(),
+            contest,
+            artwork,
+        )])
+    }
+
+    fn reject(&self, contest_address: &Pubkey, artwork_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let artwork: Artwork = self.fetch(artwork_address)?;
+        let nft_mint = artwork_nft_mint(&self.rpc, contest_address, &artwork)?;
+        let mut ixs = vec![];
+        // the NFT goes back to the token account it was submitted from while the artist still
+        // owns it, to the artist's associated token account otherwise
+        let submitted_from = self.rpc.get_account_data(&artwork.artwork_token_account)?;
+        let artist_nft_token_account = match submitted_from {
+            Some(data)
+                if data.len() >= 64
+                    && data[..32] == nft_mint.to_bytes()
+                    && data[32..64] == artwork.artist_key.to_bytes() =>
+            {
+                artwork.artwork_token_account
+            }
+            _ => {
+                let address = get_associated_token_address(&artwork.artist_key, &nft_mint);
+                if self.rpc.get_account_data(&address)?.is_none() {
+                    ixs.push(create_associated_token_account(
+                        &self.payer.pubkey(),
+                        &artwork.artist_key,
+                        &nft_mint,
+                    ));
+                }
+                address
+            }
+        };
+        ixs.push(with_legacy_seeds(
+            instructions::reject_artwork(
+                &self.payer.pubkey(),
+                contest_address,
+                &artwork.artist_key,
+                artwork.entry_index,
+                &nft_mint,
+                &artist_nft_token_account,
+            ),
+            &contest,
+            &[&artwork],
+        ));
+        self.send(&ixs)
+    }
+
+    fn vote(&self, contest_address: &Pubkey, artwork_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let artwork: Artwork = self.fetch(artwork_address)?;
+        let mut ixs = vec![];
+        let voter_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(instructions::vote(
+            &self.payer.pubkey(),
+            contest_address,
+            artwork_address,
+            artwork.artwork_id,
+            &contest.prize_mint,
+            &voter_token_account,
+        ));
+        self.send(&ixs)
+    }
+
+    fn unlock(&self, contest_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let mut ixs = vec![];
+        let voter_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(instructions::unlock_vote(
+            &self.payer.pubkey(),
+            contest_address,
+            &voter_token_account,
+        ));
+        self.send(&ixs)
+    }
+
+    fn tally(&self, contest_address: &Pubkey) -> Result<()> {
+        let mut contest: Contest = self.fetch(contest_address)?;
+        let artworks: Vec<Pubkey> = self
+            .artworks(&contest)?
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect();
+        while !contest.is_tallied() {
+            let next = contest.tallied_artwork_count as usize;
+            let end = artworks.len().min(next + TALLY_BATCH_SIZE);
+            let batch = match artworks.get(next..end) {
+                Some(batch) => batch,
+                None => bail!(
+                    "found {} artworks but {} are already tallied, the RPC node may be lagging",
+                    artworks.len(),
+                    next
+                ),
+            };
+            self.send(&[instructions::tally_votes(contest_address, batch)])?;
+            let progress = (contest.tally_pass, contest.tallied_artwork_count);
+            contest = self.fetch(contest_address)?;
+            if (contest.tally_pass, contest.tallied_artwork_count) == progress {
+                bail!("tally made no progress");
+            }
+        }
+        println!(
+            "Tallied:              {} artworks, {} votes",
+            contest.artwork_count, contest.total_vote_count
+        );
+        Ok(())
+    }
+
+    fn finalize(&self, contest_address: &Pubkey) -> Result<()> {
+        self.send(&[instructions::finalize_contest(contest_address)])?;
+        let contest: Contest = self.fetch(contest_address)?;
+        println!("Winners:              {:?}", contest.winning_artwork_ids);
+        Ok(())
+    }
+
+    fn distribute(&self, contest_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let mut voters = vec![];
+        for (_, data) in self.rpc.get_program_accounts(
+            &nft_contest::ID,
+            &filters::votes_by_contest(contest_address),
+        )? {
+            let vote_data = accounts::decode_vote_data(&data)?;
+            if vote_data.reward_claimed {
+                continue;
+            }
+            let destination =
+                get_associated_token_address(&vote_data.voter_key, &contest.prize_mint);
+            if self.rpc.get_account_data(&destination)?.is_none() {
+                println!(
+                    "Skipped:              {} has no prize token account",
+                    vote_data.voter_key
+                );
+                continue;
+            }
+            voters.push((vote_data.voter_key, destination));
+        }
+        for batch in voters.chunks(DISTRIBUTE_BATCH_SIZE) {
+            self.send(&[with_legacy_seeds(
+                instructions::distribute(contest_address, &contest.prize_mint, batch),
+                &contest,
+                &[],
+            )])?;
+        }
+        println!("Distributed:          {} votes", voters.len());
+        Ok(())
+    }
+
+    fn claim_by_artist(&self, contest_address: &Pubkey, entry_index: u8) -> Result<()> {
+        let artist = self.payer.pubkey();
+        let contest: Contest = self.fetch(contest_address)?;
+        let artwork: Artwork =
+            self.fetch(&self.artwork_address(contest_address, &contest, &artist, entry_index)?)?;
+        let mut ixs = vec![];
+        let artist_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(with_legacy_seeds(
+            instructions::claim_by_artist(
+                &artist,
+                contest_address,
+                entry_index,
+                &contest.prize_mint,
+                &artist_token_account,
+            ),
+            &contest,
+            &[&artwork],
+        ));
+        self.send(&ixs)
+    }
+
+    fn claim_by_voter(&self, contest_address: &Pubkey) -> Result<()> {
+        let voter = self.payer.pubkey();
+        let contest: Contest = self.fetch(contest_address)?;
+        let vote_data: VoteData =
+            self.fetch(&pda::find_vote_data_address(contest_address, &voter).0)?;
+        let (artwork_address, _) = self
+            .artworks(&contest)?
+            .into_iter()
+            .find(|(_, artwork)| artwork.artwork_id == vote_data.voted_artwork_id)
+            .ok_or_else(|| anyhow!("voted artwork not found"))?;
+        let mut ixs = vec![];
+        let voter_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(with_legacy_seeds(
+            instructions::claim_by_voter(
+                &voter,
+                contest_address,
+                &artwork_address,
+                &contest.prize_mint,
+                &voter_token_account,
+            ),
+            &contest,
+            &[],
+        ));
+        self.send(&ixs)
+    }
+
+    fn claim_by_contest_owner(
+        &self,
+        contest_address: &Pubkey,
+        artist: &Pubkey,
+        entry_index: u8,
+    ) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let artwork: Artwork =
+            self.fetch(&self.artwork_address(contest_address, &contest, artist, entry_index)?)?;
+        let nft_mint = artwork_nft_mint(&self.rpc, contest_address, &artwork)?;
+        let mut ixs = vec![];
+        let owner_nft_token_account = self.token_account(&nft_mint, &mut ixs)?;
+        ixs.push(with_legacy_seeds(
+            instructions::claim_by_contest_owner(
+                &self.payer.pubkey(),
+                contest_address,
+                artist,
+                entry_index,
+                &nft_mint,
+                &owner_nft_token_account,
+            ),
+            &contest,
+            &[&artwork],
+        ));
+        self.send(&ixs)
+    }
+
+    fn sweep(&self, contest_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        let mut ixs = vec![];
+        let owner_token_account = self.token_account(&contest.prize_mint, &mut ixs)?;
+        ixs.push(with_legacy_seeds(
+            instructions::sweep_remainder(
+                &self.payer.pubkey(),
+                contest_address,
+                &contest.prize_mint,
+                &owner_token_account,
+            ),
+            &contest,
+            &[],
+        ));
+        self.send(&ixs)
+    }
+
+    fn migrate(&self, address: &Pubkey, contest: Option<&Pubkey>) -> Result<()> {
+        let data = self
+            .rpc
+            .get_account_data(address)?
+            .ok_or_else(|| anyhow!("account {} not found", address))?;
+        let admin = self.payer.pubkey();
+        let discriminator = data.get(..8).unwrap_or_default();
+        let contest = || contest.ok_or_else(|| anyhow!("--contest is required for {}", address));
+        let ix = if discriminator == Counter::discriminator() {
+            instructions::migrate_counter(&admin)
+        } else if discriminator == Contest::discriminator() {
+            let legacy = accounts::decode_contest(&data)?;
+            let legacy_address =
+                pda::find_legacy_contest_address(&legacy.contest_owner, legacy.contest_id).0;
+            if legacy_address != *address {
+                bail!("contest {} was not launched by the first release", address);
+            }
+            // the program checks that every one of them was migrated first
+            let artworks: Vec<Pubkey> = self
+                .artworks(&legacy)?
+                .into_iter()
+                .filter(|(artwork_address, artwork)| {
+                    *artwork_address
+                        == pda::find_legacy_artwork_address(address, &artwork.artist_key).0
+                })
+                .map(|(artwork_address, _)| artwork_address)
+                .collect();
+            instructions::migrate_contest(
+                &admin,
+                &legacy.contest_owner,
+                legacy.contest_id,
+                &artworks,
+            )
+        } else if discriminator == Artwork::discriminator() {
+            instructions::migrate_artwork(&admin, contest()?, address)
+        } else if discriminator == VoteData::discriminator() {
+            instructions::migrate_vote_data(&admin, contest()?, address)
+        } else {
+            bail!("account {} has no versioned layout", address);
+        };
+        self.send(&[ix])
+    }
+
+    fn status(&self, contest_address: &Pubkey) -> Result<()> {
+        let contest: Contest = self.fetch(contest_address)?;
+        println!("Contest:              {}", contest_address);
+        println!("Id:                   {}", contest.contest_id);
+        println!("Title:                {}", contest.title_of_contest);
+        println!("Owner:                {}", contest.contest_owner);
+        println!(
+            "Status:               {:?}",
+            contest.current_status(self.now()?)
+        );
+        println!(
+            "Prize:                {} of {}",
+            contest.prize_amount, contest.prize_mint
+        );
+        println!("Artist share:         {}%", contest.percentage_to_artist);
+        println!("Voter reward mode:    {:?}", contest.voter_reward_mode);
+        println!(
+            "Submissions:          {} - {}",
+            contest.submit_start_at, contest.submit_end_at
+        );
+        println!(
+            "Voting:               {} - {}",
+            contest.vote_start_at, contest.vote_end_at
+        );
+        println!("Claims until:         {}", contest.claim_end_at);
+        println!(
+            "Artworks:             {} / {}",
+            contest.artwork_count, contest.max_artworks
+        );
+        println!(
+            "Tallied:              {} (total votes {})",
+            contest.is_tallied(),
+            contest.total_vote_count
+        );
+        println!("Finalized:            {}", contest.is_finalized);
+        if contest.is_finalized {
+            println!("Winners:              {:?}", contest.winning_artwork_ids);
+            println!(
+                "Artist payout:        {} per winner",
+                contest.artist_payout_per_winner
+            );
+            println!(
+                "Voter payout:         {} per vote",
+                contest.voter_payout_per_vote
+            );
+        }
+
+        println!();
+        println!(
+            "{:>4}  {:<44}  {:<44}  {:<9}  {:>6}  {:>6}",
+            "ID", "ARTWORK", "ARTIST", "STATUS", "VOTES", "JUDGES"
+        );
+        for (address, artwork) in self.artworks(&contest)? {
+            println!(
+                "{:>4}  {:<44}  {:<44}  {:<9}  {:>6}  {:>6}",
+                artwork.artwork_id,
+                address.to_string(),
+                artwork.artist_key.to_string(),
+                format!("{:?}", artwork.status),
+                artwork.vote_count,
+                artwork.judge_score_total
+            );
+        }
+        Ok(())
+    }
+}
+
+fn simulate(spec: &ContestSpec, tallies: &TalliesSpec, weight: u64) -> Result<()> {
+    let contest = simulate::launched_contest(&spec.to_launch_args()?);
+    let simulation = Simulation::run(&contest, &tallies.to_tallies())?;
+    let contest = &simulation.contest;
+    let vote_weighted = contest.voter_reward_mode == VoterRewardMode::VoteWeighted;
+    if vote_weighted && weight > contest.total_vote_weight {
+        bail!(
+            "--weight {} exceeds the total vote weight {} of the tallies",
+            weight,
+            contest.total_vote_weight
+        );
+    }
+    println!("Prize:                {}", contest.prize_amount);
+    println!("Voter reward mode:    {:?}", contest.voter_reward_mode);
+    println!(
+        "Total votes:          {} (weight {})",
+        contest.total_vote_count, contest.total_vote_weight
+    );
+    println!(
+        "Winners:              {:?}",
+        simulation.winning_artwork_ids()
+    );
+    println!(
+        "Artist payout:        {} per winner, vested over {}s after voting",
+        contest.artist_payout_per_winner, contest.artist_vesting_duration
+    );
+    println!("Voter pool:           {}", contest.voter_pool_amount);
+
+    println!();
+    println!(
+        "{:>4}  {:<9}  {:>6}  {:>10}  {:>6}  {:>6}  {:>14}  {:>14}",
+        "ID", "STATUS", "VOTES", "WEIGHT", "JUDGES", "WINNER", "ARTIST PRIZE", "VOTER REWARD"
+    );
+    for artwork in &simulation.artworks {
+        // no vote for this artwork can weigh more than all of its votes together
+        let voter_reward = if vote_weighted && weight > artwork.vote_weight_total {
+            "-".to_string()
+        } else {
+            simulation
+                .voter_reward(artwork.artwork_id, weight)?
+                .to_string()
+        };
+        println!(
+            "{:>4}  {:<9}  {:>6}  {:>10}  {:>6}  {:>6}  {:>14}  {:>14}",
+            artwork.artwork_id,
+            format!("{:?}", artwork.status),
+            artwork.vote_count,
+            artwork.vote_weight_total,
+            artwork.judge_score_total,
+            if contest.is_winner(artwork.artwork_id) {
+                "yes"
+            } else {
+                "no"
+            },
+            simulation.artist_prize(artwork.artwork_id),
+            voter_reward
+        );
+    }
+    Ok(())
+}
+
+/// Points `ix` at the legacy addresses of `contest` when it was migrated from the first release,
+/// and at those of the artworks among `artworks` migrated with it.
+fn with_legacy_seeds(ix: Instruction, contest: &Contest, artworks: &[&Artwork]) -> Instruction {
+    if !contest.legacy_seeds {
+        return ix;
+    }
+    let artists: Vec<Pubkey> = artworks
+        .iter()
+        .filter(|artwork| artwork.legacy_seeds)
+        .map(|artwork| artwork.artist_key)
+        .collect();
+    instructions::with_legacy_seeds(ix, &contest.contest_owner, contest.contest_id, &artists)
+}
+
+/// The NFT of an artwork is the mint of its vault, which outlives the token account it was
+/// submitted from.
+fn artwork_nft_mint(rpc: &RpcClient, contest: &Pubkey, artwork: &Artwork) -> Result<Pubkey> {
+    let vault = if artwork.legacy_seeds {
+        pda::find_legacy_nft_vault_address(contest, &artwork.artist_key).0
+    } else {
+        pda::find_nft_vault_address(contest, &artwork.artist_key, artwork.entry_index).0
+    };
+    let data = rpc
+        .get_account_data(&vault)?
+        .ok_or_else(|| anyhow!("NFT vault {} not found", vault))?;
+    if data.len() < 32 {
+        bail!("NFT vault {} is not a token account", vault);
+    }
+    Ok(Pubkey::new(&data[..32]))
+}
@@ -0,0 +1,156 @@
+use anyhow::{bail, Context, Result};
+use nft_contest::state::{ContestMetadata, VoterRewardMode};
+use nft_contest_client::instructions::LaunchArgs;
+use nft_contest_client::simulate::ArtworkTally;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Contest launched by `nft-contest launch`, read from a TOML or JSON file.
+/// Timestamps are unix seconds, amounts are raw token amounts.
+#[derive(Debug, Deserialize)]
+pub struct ContestSpec {
+    pub prize_mint: String,
+    pub prize_amount: u64,
+    pub percentage_to_artist: u8,
+    pub submit_start_at: u64,
+    pub submit_end_at: u64,
+    pub vote_start_at: u64,
+    pub vote_end_at: u64,
+    pub claim_end_at: u64,
+    pub title_of_contest: String,
+    #[serde(default)]
+    pub link_to_project: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub cover_image_uri: String,
+    pub max_artworks: u32,
+    #[serde(default)]
+    pub required_collection: Option<String>,
+    #[serde(default = "default_max_entries_per_artist")]
+    pub max_entries_per_artist: u8,
+    #[serde(default)]
+    pub curation_enabled: bool,
+    #[serde(default)]
+    pub judges: Vec<String>,
+    #[serde(default)]
+    pub judge_weight_percent: u8,
+    #[serde(default)]
+    pub artist_vesting_duration: u64,
+    #[serde(default)]
+    pub voter_reward_mode: VoterRewardModeSpec,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoterRewardModeSpec {
+    #[default]
+    WinnersOnly,
+    AllVoters,
+    VoteWeighted,
+    None,
+}
+
+fn default_max_entries_per_artist() -> u8 {
+    1
+}
+
+/// Hypothetical tallies simulated by `nft-contest simulate`, read from a TOML or JSON file.
+#[derive(Debug, Deserialize)]
+pub struct TalliesSpec {
+    // in order of artwork id
+    pub artworks: Vec<ArtworkTallySpec>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArtworkTallySpec {
+    #[serde(default)]
+    pub votes: u64,
+    // sum of the vote weights, defaults to one per vote
+    #[serde(default)]
+    pub vote_weight: Option<u64>,
+    #[serde(default)]
+    pub judge_score: u64,
+    #[serde(default)]
+    pub rejected: bool,
+}
+
+fn from_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => Ok(toml::from_str(&content)?),
+        Some("json") => Ok(serde_json::from_str(&content)?),
+        _ => bail!("{} must be a .toml or .json file", path.display()),
+    }
+}
+
+impl TalliesSpec {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        from_file(path)
+    }
+
+    pub fn to_tallies(&self) -> Vec<ArtworkTally> {
+        self.artworks
+            .iter()
+            .map(|artwork| ArtworkTally {
+                vote_count: artwork.votes,
+                vote_weight_total: artwork.vote_weight.unwrap_or(artwork.votes),
+                judge_score_total: artwork.judge_score,
+                rejected: artwork.rejected,
+            })
+            .collect()
+    }
+}
+
+impl ContestSpec {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        from_file(path)
+    }
+
+    pub fn prize_mint(&self) -> Result<Pubkey> {
+        Ok(Pubkey::from_str(&self.prize_mint)?)
+    }
+
+    pub fn to_launch_args(&self) -> Result<LaunchArgs> {
+        Ok(LaunchArgs {
+            prize_amount: self.prize_amount,
+            percentage_to_artist: self.percentage_to_artist,
+            submit_start_at: self.submit_start_at,
+            submit_end_at: self.submit_end_at,
+            vote_start_at: self.vote_start_at,
+            vote_end_at: self.vote_end_at,
+            metadata: ContestMetadata {
+                title_of_contest: self.title_of_contest.clone(),
+                link_to_project: self.link_to_project.clone(),
+                description: self.description.clone(),
+                cover_image_uri: self.cover_image_uri.clone(),
+            },
+            max_artworks: self.max_artworks,
+            required_collection: self
+                .required_collection
+                .as_deref()
+                .map(Pubkey::from_str)
+                .transpose()?,
+            max_entries_per_artist: self.max_entries_per_artist,
+            curation_enabled: self.curation_enabled,
+            judges: self
+                .judges
+                .iter()
+                .map(|judge| Pubkey::from_str(judge))
+                .collect::<Result<_, _>>()?,
+            judge_weight_percent: self.judge_weight_percent,
+            artist_vesting_duration: self.artist_vesting_duration,
+            voter_reward_mode: match self.voter_reward_mode {
+                VoterRewardModeSpec::WinnersOnly => VoterRewardMode::WinnersOnly,
+                VoterRewardModeSpec::AllVoters => VoterRewardMode::AllVoters,
+                VoterRewardModeSpec::VoteWeighted => VoterRewardMode::VoteWeighted,
+                VoterRewardModeSpec::None => VoterRewardMode::None,
+            },
+            claim_end_at: self.claim_end_at,
+        })
+    }
+}
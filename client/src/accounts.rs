@@ -0,0 +1,47 @@
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator, Result};
+use nft_contest::state::legacy::{ArtworkV0, ContestV0, CounterV0, VoteDataV0};
+
+pub use nft_contest::state::{
+    ArtistEntries, Artwork, ArtworkStatus, Contest, ContestMetadata, ContestStatus, Counter,
+    JudgeScore, OwnerContestPage, OwnerProfile, VoteData, VoterRewardMode,
+};
+
+/// Decodes raw account data, checking the account discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+/// Decodes raw account data like `decode`, upgrading accounts still in the legacy layout `L` with
+/// its From impl. Fields the migrate_* instructions derive from other accounts, like bumps, the
+/// prize mint of a contest or the votes of an artwork, are left defaulted.
+pub fn decode_versioned<T, L>(data: &[u8]) -> Result<T>
+where
+    T: AccountDeserialize + Discriminator,
+    L: AnchorDeserialize + Into<T>,
+{
+    decode::<T>(data).or_else(|err| {
+        if data.len() < 8 || data[..8] != T::discriminator() {
+            return Err(err);
+        }
+        L::deserialize(&mut &data[8..])
+            .map(Into::into)
+            .map_err(|_| err)
+    })
+}
+
+pub fn decode_counter(data: &[u8]) -> Result<Counter> {
+    decode_versioned::<Counter, CounterV0>(data)
+}
+
+pub fn decode_contest(data: &[u8]) -> Result<Contest> {
+    decode_versioned::<Contest, ContestV0>(data)
+}
+
+pub fn decode_artwork(data: &[u8]) -> Result<Artwork> {
+    decode_versioned::<Artwork, ArtworkV0>(data)
+}
+
+pub fn decode_vote_data(data: &[u8]) -> Result<VoteData> {
+    decode_versioned::<VoteData, VoteDataV0>(data)
+}
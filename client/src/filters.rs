@@ -0,0 +1,62 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use nft_contest::state::{Artwork, Contest, VoteData};
+
+/// getProgramAccounts filter matching `bytes` at `offset` of the account data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Memcmp {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl Memcmp {
+    pub fn new(offset: usize, bytes: &[u8]) -> Self {
+        Self {
+            offset,
+            bytes: bytes.to_vec(),
+        }
+    }
+}
+
+/// Matches the accounts of type `T` by their discriminator.
+pub fn account_type<T: Discriminator>() -> Memcmp {
+    Memcmp::new(0, &T::discriminator())
+}
+
+pub fn contests_by_owner(owner: &Pubkey) -> Vec<Memcmp> {
+    vec![
+        account_type::<Contest>(),
+        Memcmp::new(Contest::CONTEST_OWNER_OFFSET, owner.as_ref()),
+    ]
+}
+
+/// Artworks reference their contest by `Contest::contest_id`.
+pub fn artworks_by_contest(contest_id: u64) -> Vec<Memcmp> {
+    vec![
+        account_type::<Artwork>(),
+        Memcmp::new(Artwork::CONTEST_ID_OFFSET, &contest_id.to_le_bytes()),
+    ]
+}
+
+pub fn votes_by_voter(voter: &Pubkey) -> Vec<Memcmp> {
+    vec![
+        account_type::<VoteData>(),
+        Memcmp::new(VoteData::VOTER_OFFSET, voter.as_ref()),
+    ]
+}
+
+pub fn votes_by_contest(contest: &Pubkey) -> Vec<Memcmp> {
+    vec![
+        account_type::<VoteData>(),
+        Memcmp::new(VoteData::CONTEST_OFFSET, contest.as_ref()),
+    ]
+}
+
+pub fn votes_by_artwork(contest: &Pubkey, artwork_id: u64) -> Vec<Memcmp> {
+    let mut filters = votes_by_contest(contest);
+    filters.push(Memcmp::new(
+        VoteData::VOTED_ARTWORK_ID_OFFSET,
+        &artwork_id.to_le_bytes(),
+    ));
+    filters
+}
@@ -0,0 +1,468 @@
+use crate::pda::*;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use nft_contest::{accounts, instruction};
+
+pub use nft_contest::state::LaunchArgs;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nft_contest::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(program_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            program_owner: *program_owner,
+            counter: find_counter_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::Initialize {},
+    )
+}
+
+/// `contest_id` is the current `Counter::contest_count` and `owner_contest_count` the current
+/// `OwnerProfile::contest_count` of the owner, 0 before their first contest.
+pub fn launch(
+    contest_owner: &Pubkey,
+    contest_id: u64,
+    owner_contest_count: u64,
+    prize_mint: &Pubkey,
+    prize_token_account: &Pubkey,
+    args: LaunchArgs,
+) -> Instruction {
+    let contest = find_contest_address(contest_owner, contest_id).0;
+    build(
+        accounts::Launch {
+            contest_owner: *contest_owner,
+            counter: find_counter_address().0,
+            contest,
+            owner_profile: find_owner_profile_address(contest_owner).0,
+            owner_contest_page: find_owner_contest_page_address(
+                contest_owner,
+                owner_contest_page_index(owner_contest_count),
+            )
+            .0,
+            prize_mint: *prize_mint,
+            prize_vault_account: find_prize_vault_address(&contest).0,
+            prize_token_account: *prize_token_account,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Launch { args },
+    )
+}
+
+/// `destination_token_account` receives the refunded prize, the vault rent goes to the owner.
+pub fn cancel_contest(
+    contest_owner: &Pubkey,
+    contest: &Pubkey,
+    prize_mint: &Pubkey,
+    destination_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelContest {
+            contest_owner: *contest_owner,
+            contest: *contest,
+            prize_mint: *prize_mint,
+            prize_vault_account: find_prize_vault_address(contest).0,
+            prize_vault_authority: find_prize_vault_authority_address(contest).0,
+            destination_token_account: *destination_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::CancelContest {},
+    )
+}
+
+/// `entry_index` is the current `ArtistEntries::entry_count` of the artist, 0 for a first entry.
+pub fn submit(
+    artist: &Pubkey,
+    contest: &Pubkey,
+    entry_index: u8,
+    nft_mint: &Pubkey,
+    artwork_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::Submit {
+            artist: *artist,
+            contest: *contest,
+            artist_entries: find_artist_entries_address(contest, artist).0,
+            artwork: find_artwork_address(contest, artist, entry_index).0,
+            nft_mint: *nft_mint,
+            nft_metadata: find_metadata_address(nft_mint).0,
+            nft_vault_account: find_nft_vault_address(contest, artist, entry_index).0,
+            artwork_token_account: *artwork_token_account,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Submit {},
+    )
+}
+
+pub fn set_curators(
+    contest_owner: &Pubkey,
+    contest: &Pubkey,
+    curators: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::SetCurators {
+            contest_owner: *contest_owner,
+            contest: *contest,
+        },
+        instruction::SetCurators { curators },
+    )
+}
+
+/// `reviewer` is the contest owner or one of its curators.
+pub fn approve_artwork(reviewer: &Pubkey, contest: &Pubkey, artwork: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveArtwork {
+            reviewer: *reviewer,
+            contest: *contest,
+            artwork: *artwork,
+        },
+        instruction::ApproveArtwork {},
+    )
+}
+
+/// Rejects the entry `entry_index` of `artist`, returning its NFT to `artist_nft_token_account`.
+pub fn reject_artwork(
+    reviewer: &Pubkey,
+    contest: &Pubkey,
+    artist: &Pubkey,
+    entry_index: u8,
+    nft_mint: &Pubkey,
+    artist_nft_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::RejectArtwork {
+            reviewer: *reviewer,
+            contest: *contest,
+            artwork: find_artwork_address(contest, artist, entry_index).0,
+            nft_mint: *nft_mint,
+            nft_vault_account: find_nft_vault_address(contest, artist, entry_index).0,
+            nft_vault_authority: find_nft_vault_authority_address(contest, artist).0,
+            artist_nft_token_account: *artist_nft_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::RejectArtwork {},
+    )
+}
+
+/// Returns the NFT of an entry left pending when voting started.
+pub fn withdraw_artwork(
+    artist: &Pubkey,
+    contest: &Pubkey,
+    entry_index: u8,
+    nft_mint: &Pubkey,
+    artist_nft_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::WithdrawArtwork {
+            artist: *artist,
+            contest: *contest,
+            artwork: find_artwork_address(contest, artist, entry_index).0,
+            nft_mint: *nft_mint,
+            nft_vault_account: find_nft_vault_address(contest, artist, entry_index).0,
+            nft_vault_authority: find_nft_vault_authority_address(contest, artist).0,
+            artist_nft_token_account: *artist_nft_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::WithdrawArtwork {},
+    )
+}
+
+/// `artwork` is the address of the voted artwork, whose id is `voted_artwork_id`.
+pub fn vote(
+    voter: &Pubkey,
+    contest: &Pubkey,
+    artwork: &Pubkey,
+    voted_artwork_id: u64,
+    prize_mint: &Pubkey,
+    voter_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::Vote {
+            voter: *voter,
+            artwork: *artwork,
+            contest: *contest,
+            vote_data: find_vote_data_address(contest, voter).0,
+            prize_mint: *prize_mint,
+            voter_token_account: *voter_token_account,
+            vote_escrow: find_vote_escrow_address(contest, voter).0,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Vote { voted_artwork_id },
+    )
+}
+
+/// Returns the prize tokens locked by a vote in a contest rewarding voters by weight.
+pub fn unlock_vote(voter: &Pubkey, contest: &Pubkey, voter_token_account: &Pubkey) -> Instruction {
+    build(
+        accounts::UnlockVote {
+            voter: *voter,
+            contest: *contest,
+            vote_escrow: find_vote_escrow_address(contest, voter).0,
+            voter_token_account: *voter_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::UnlockVote {},
+    )
+}
+
+/// `artworks` are the artwork addresses to tally in this transaction, in order of artwork id.
+pub fn tally_votes(contest: &Pubkey, artworks: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::TallyVotes { contest: *contest },
+        instruction::TallyVotes {},
+    );
+    ix.accounts.extend(
+        artworks
+            .iter()
+            .map(|artwork| AccountMeta::new_readonly(*artwork, false)),
+    );
+    ix
+}
+
+pub fn finalize_contest(contest: &Pubkey) -> Instruction {
+    build(
+        accounts::FinalizeContest { contest: *contest },
+        instruction::FinalizeContest {},
+    )
+}
+
+pub fn claim_by_artist(
+    artist: &Pubkey,
+    contest: &Pubkey,
+    entry_index: u8,
+    prize_mint: &Pubkey,
+    artist_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimByArtist {
+            artist: *artist,
+            artwork: find_artwork_address(contest, artist, entry_index).0,
+            contest: *contest,
+            prize_mint: *prize_mint,
+            prize_vault_account: find_prize_vault_address(contest).0,
+            prize_vault_authority: find_prize_vault_authority_address(contest).0,
+            artist_token_account: *artist_token_account,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimByArtist {},
+    )
+}
+
+/// `artwork` is the address of the artwork the voter voted for.
+pub fn claim_by_voter(
+    voter: &Pubkey,
+    contest: &Pubkey,
+    artwork: &Pubkey,
+    prize_mint: &Pubkey,
+    voter_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimByVoter {
+            voter: *voter,
+            artwork: *artwork,
+            contest: *contest,
+            prize_mint: *prize_mint,
+            vote_data: find_vote_data_address(contest, voter).0,
+            prize_vault_account: find_prize_vault_address(contest).0,
+            prize_vault_authority: find_prize_vault_authority_address(contest).0,
+            voter_token_account: *voter_token_account,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimByVoter {},
+    )
+}
+
+/// `voters` are pairs of a voter and their prize token account.
+pub fn distribute(
+    contest: &Pubkey,
+    prize_mint: &Pubkey,
+    voters: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut ix = build(
+        accounts::Distribute {
+            contest: *contest,
+            prize_mint: *prize_mint,
+            prize_vault_account: find_prize_vault_address(contest).0,
+            prize_vault_authority: find_prize_vault_authority_address(contest).0,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::Distribute {},
+    );
+    for (voter, voter_token_account) in voters {
+        ix.accounts.push(AccountMeta::new(
+            find_vote_data_address(contest, voter).0,
+            false,
+        ));
+        ix.accounts
+            .push(AccountMeta::new(*voter_token_account, false));
+    }
+    ix
+}
+
+/// Claims the NFT of the artwork submitted by `artist` as their entry `entry_index`.
+pub fn claim_by_contest_owner(
+    contest_owner: &Pubkey,
+    contest: &Pubkey,
+    artist: &Pubkey,
+    entry_index: u8,
+    nft_mint: &Pubkey,
+    contest_owner_nft_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimByContestOwner {
+            contest_owner: *contest_owner,
+            contest: *contest,
+            artwork: find_artwork_address(contest, artist, entry_index).0,
+            nft_mint: *nft_mint,
+            nft_vault_account: find_nft_vault_address(contest, artist, entry_index).0,
+            nft_vault_authority: find_nft_vault_authority_address(contest, artist).0,
+            contest_owner_nft_token_account: *contest_owner_nft_token_account,
+            rent: sysvar::rent::ID,
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimByContestOwner {},
+    )
+}
+
+pub fn sweep_remainder(
+    contest_owner: &Pubkey,
+    contest: &Pubkey,
+    prize_mint: &Pubkey,
+    destination_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::SweepRemainder {
+            contest_owner: *contest_owner,
+            contest: *contest,
+            prize_mint: *prize_mint,
+            prize_vault_account: find_prize_vault_address(contest).0,
+            prize_vault_authority: find_prize_vault_authority_address(contest).0,
+            destination_token_account: *destination_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SweepRemainder {},
+    )
+}
+
+/// `admin` is the upgrade authority of the program.
+pub fn migrate_counter(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateCounter {
+            admin: *admin,
+            program_data: find_program_data_address().0,
+            counter: find_counter_address().0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateCounter {},
+    )
+}
+
+/// Points `ix`, built for the contest numbered `contest_id` of `contest_owner`, at the legacy
+/// addresses that a contest migrated from the first release keeps: the builders above derive its
+/// prize vault, and the artworks and NFT vaults of `artists`, with the current seeds.
+/// `artists` are those whose first release artwork `ix` addresses, as entry 0.
+pub fn with_legacy_seeds(
+    mut ix: Instruction,
+    contest_owner: &Pubkey,
+    contest_id: u64,
+    artists: &[Pubkey],
+) -> Instruction {
+    let contest = find_legacy_contest_address(contest_owner, contest_id).0;
+    let mut addresses = vec![(
+        find_prize_vault_address(&contest).0,
+        find_legacy_prize_vault_address(contest_owner, contest_id).0,
+    )];
+    for artist in artists {
+        addresses.push((
+            find_artwork_address(&contest, artist, 0).0,
+            find_legacy_artwork_address(&contest, artist).0,
+        ));
+        addresses.push((
+            find_nft_vault_address(&contest, artist, 0).0,
+            find_legacy_nft_vault_address(&contest, artist).0,
+        ));
+    }
+    for meta in &mut ix.accounts {
+        if let Some((_, legacy)) = addresses
+            .iter()
+            .find(|(current, _)| *current == meta.pubkey)
+        {
+            meta.pubkey = *legacy;
+        }
+    }
+    ix
+}
+
+/// `contest_owner` and `contest_id` give the legacy addresses of the contest and its prize vault.
+/// `artworks` are the addresses of all its artworks, already migrated.
+pub fn migrate_contest(
+    admin: &Pubkey,
+    contest_owner: &Pubkey,
+    contest_id: u64,
+    artworks: &[Pubkey],
+) -> Instruction {
+    let contest = find_legacy_contest_address(contest_owner, contest_id).0;
+    let mut ix = build(
+        accounts::MigrateContest {
+            admin: *admin,
+            program_data: find_program_data_address().0,
+            contest,
+            prize_vault_account: find_legacy_prize_vault_address(contest_owner, contest_id).0,
+            prize_vault_authority: find_prize_vault_authority_address(&contest).0,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateContest {},
+    );
+    ix.accounts.extend(
+        artworks
+            .iter()
+            .map(|artwork| AccountMeta::new_readonly(*artwork, false)),
+    );
+    ix
+}
+
+/// `contest` must still be in the legacy layout.
+pub fn migrate_artwork(admin: &Pubkey, contest: &Pubkey, artwork: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateArtwork {
+            admin: *admin,
+            program_data: find_program_data_address().0,
+            contest: *contest,
+            artwork: *artwork,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateArtwork {},
+    )
+}
+
+pub fn migrate_vote_data(admin: &Pubkey, contest: &Pubkey, vote_data: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateVoteData {
+            admin: *admin,
+            program_data: find_program_data_address().0,
+            contest: *contest,
+            vote_data: *vote_data,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateVoteData {},
+    )
+}
@@ -0,0 +1,13 @@
+//! Client for the nft_contest program: instruction builders, PDA derivation helpers matching the
+//! program's seeds, decoders for its accounts, getProgramAccounts filters, a minimal JSON-RPC
+//! client, and an off-chain simulation of contest payouts.
+
+pub mod accounts;
+pub mod filters;
+pub mod instructions;
+pub mod pda;
+pub mod rpc;
+pub mod simulate;
+
+pub use nft_contest::ID;
+pub use pda::*;
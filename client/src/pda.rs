@@ -0,0 +1,14 @@
+use anchor_lang::prelude::Pubkey;
+use nft_contest::state::CONTESTS_PER_PAGE;
+
+pub use nft_contest::pda::*;
+
+/// Metaplex metadata account of an NFT mint, required by submit.
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    mpl_token_metadata::pda::find_metadata_account(mint)
+}
+
+/// Page of the owner's contest list that the next launch appends to.
+pub fn owner_contest_page_index(owner_contest_count: u64) -> u64 {
+    owner_contest_count / CONTESTS_PER_PAGE as u64
+}
@@ -0,0 +1,196 @@
+use crate::filters::Memcmp;
+use anyhow::{anyhow, bail, Result};
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const CONFIRM_ATTEMPTS: usize = 60;
+// most signatures getSignaturesForAddress returns at once
+const SIGNATURES_PAGE_LIMIT: usize = 1000;
+
+/// Minimal JSON-RPC client for the few methods the CLI and the indexer need.
+pub struct RpcClient {
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            bail!("{} failed: {}", method, error);
+        }
+        Ok(response["result"].clone())
+    }
+
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        match result["value"].as_object() {
+            Some(account) => Ok(Some(decode_data(&account["data"])?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Accounts owned by `program_id` matching every filter, as `{ "pubkey", "account" }` objects,
+    /// so that they can be parsed the same way as the entries of a dump.
+    pub fn get_keyed_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        memcmps: &[Memcmp],
+    ) -> Result<Vec<Value>> {
+        let filters: Vec<Value> = memcmps
+            .iter()
+            .map(|memcmp| {
+                json!({ "memcmp": {
+                    "offset": memcmp.offset,
+                    "bytes": bs58::encode(&memcmp.bytes).into_string(),
+                }})
+            })
+            .collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), {
+                "encoding": "base64",
+                "commitment": "confirmed",
+                "filters": filters,
+            }]),
+        )?;
+        match result {
+            Value::Array(accounts) => Ok(accounts),
+            _ => Err(anyhow!("unexpected getProgramAccounts result")),
+        }
+    }
+
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        memcmps: &[Memcmp],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        self.get_keyed_program_accounts(program_id, memcmps)?
+            .iter()
+            .map(|keyed_account| {
+                let address = Pubkey::from_str(keyed_account["pubkey"].as_str().unwrap_or(""))?;
+                Ok((address, decode_data(&keyed_account["account"]["data"])?))
+            })
+            .collect()
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("unexpected getLatestBlockhash result"))?;
+        Ok(Hash::from_str(blockhash)?)
+    }
+
+    /// Signatures of the transactions mentioning `address` after `until`, oldest first.
+    pub fn get_signatures_since(
+        &self,
+        address: &Pubkey,
+        until: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let mut signatures = vec![];
+        let mut before: Option<String> = None;
+        loop {
+            let result = self.call(
+                "getSignaturesForAddress",
+                json!([address.to_string(), {
+                    "limit": SIGNATURES_PAGE_LIMIT,
+                    "before": before,
+                    "until": until,
+                    "commitment": "confirmed",
+                }]),
+            )?;
+            let page = result
+                .as_array()
+                .ok_or_else(|| anyhow!("unexpected getSignaturesForAddress result"))?;
+            for entry in page {
+                let signature = entry["signature"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("unexpected getSignaturesForAddress entry"))?;
+                signatures.push(signature.to_string());
+            }
+            if page.len() < SIGNATURES_PAGE_LIMIT {
+                break;
+            }
+            before = signatures.last().cloned();
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    pub fn get_transaction(&self, signature: &str) -> Result<Value> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": "confirmed",
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        if result.is_null() {
+            bail!("transaction {} not found", signature);
+        }
+        Ok(result)
+    }
+
+    /// Sends the transaction and waits until it is confirmed.
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<Signature> {
+        let encoded = base64::encode(bincode::serialize(transaction)?);
+        let result = self.call(
+            "sendTransaction",
+            json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+        )?;
+        let signature = Signature::from_str(
+            result
+                .as_str()
+                .ok_or_else(|| anyhow!("unexpected sendTransaction result"))?,
+        )?;
+
+        for _ in 0..CONFIRM_ATTEMPTS {
+            let result = self.call("getSignatureStatuses", json!([[signature.to_string()]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {} failed: {}", signature, status["err"]);
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed") | Some("finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+            sleep(CONFIRM_POLL_INTERVAL);
+        }
+        bail!("transaction {} was not confirmed in time", signature)
+    }
+}
+
+fn decode_data(data: &Value) -> Result<Vec<u8>> {
+    let encoded = data[0]
+        .as_str()
+        .ok_or_else(|| anyhow!("unexpected account data encoding"))?;
+    Ok(base64::decode(encoded)?)
+}
@@ -0,0 +1,182 @@
+use crate::instructions::LaunchArgs;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Result;
+use nft_contest::state::{
+    Artwork, ArtworkStatus, Contest, ContestStatus, VoteData, RESERVED_SPACE,
+};
+
+/// Hypothetical tallies of an artwork.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArtworkTally {
+    pub vote_count: u64,
+    // sum of the weights of its votes, the voter token balances in VoteWeighted contests
+    pub vote_weight_total: u64,
+    pub judge_score_total: u64,
+    // rejected artworks are skipped by the tally, like artworks never approved by curators
+    pub rejected: bool,
+}
+
+impl From<&Artwork> for ArtworkTally {
+    fn from(artwork: &Artwork) -> Self {
+        Self {
+            vote_count: artwork.vote_count,
+            vote_weight_total: artwork.vote_weight_total,
+            judge_score_total: artwork.judge_score_total,
+            rejected: artwork.status != ArtworkStatus::Approved,
+        }
+    }
+}
+
+/// Contest tallied and finalized off-chain by the same `Contest` methods as tally_votes and
+/// finalize_contest, so that its winners and payouts are the ones the program would record.
+pub struct Simulation {
+    pub contest: Contest,
+    pub artworks: Vec<Artwork>,
+}
+
+impl Simulation {
+    /// Tallies and finalizes `contest` from scratch, with `tallies` in order of artwork id.
+    pub fn run(contest: &Contest, tallies: &[ArtworkTally]) -> Result<Self> {
+        let mut contest = contest.clone();
+        contest.artwork_count = tallies.len() as u64;
+        contest.tally_pass = 0;
+        contest.tallied_artwork_count = 0;
+        contest.total_vote_count = 0;
+        contest.total_vote_weight = 0;
+        contest.winner_score = 0;
+        contest.winning_vote_total = 0;
+        contest.winning_artwork_ids = vec![];
+        contest.is_finalized = false;
+        contest.status = ContestStatus::Voting;
+
+        let artworks: Vec<Artwork> = tallies
+            .iter()
+            .enumerate()
+            .map(|(artwork_id, tally)| simulated_artwork(&contest, artwork_id as u64, tally))
+            .collect();
+        // one tally_votes transaction per pass, with every artwork
+        while !contest.is_tallied() {
+            for artwork in &artworks {
+                contest.tally_artwork(artwork)?;
+            }
+            contest.advance_tally_pass();
+        }
+        contest.finalize()?;
+
+        Ok(Self { contest, artworks })
+    }
+
+    pub fn winning_artwork_ids(&self) -> &[u64] {
+        &self.contest.winning_artwork_ids
+    }
+
+    /// Artist prize of an artwork, once fully vested.
+    pub fn artist_prize(&self, artwork_id: u64) -> u64 {
+        if self.contest.is_winner(artwork_id) {
+            self.contest.artist_payout_per_winner
+        } else {
+            0
+        }
+    }
+
+    /// Part of the artist prize of an artwork that claim_by_artist can have paid at `now_ts`.
+    pub fn artist_vested_amount(&self, artwork_id: u64, now_ts: u64) -> Result<u64> {
+        if !self.contest.is_winner(artwork_id) {
+            return Ok(0);
+        }
+        self.contest.artist_vested_amount(now_ts)
+    }
+
+    /// Reward claim_by_voter pays for a vote for `artwork_id`. `weight` is the voter token
+    /// balance when voting in VoteWeighted contests, and 1 otherwise.
+    pub fn voter_reward(&self, artwork_id: u64, weight: u64) -> Result<u64> {
+        self.contest.voter_reward(&VoteData {
+            is_initialized: true,
+            contest: Pubkey::default(),
+            voter_key: Pubkey::default(),
+            voted_artwork_id: artwork_id,
+            weight,
+            reward_claimed: false,
+            bump: 0,
+            version: VoteData::VERSION,
+            reserved: [0; RESERVED_SPACE],
+        })
+    }
+}
+
+/// Contest as launched with `args`, before any submission.
+pub fn launched_contest(args: &LaunchArgs) -> Contest {
+    Contest {
+        is_initialized: true,
+        contest_id: 0,
+        contest_owner: Pubkey::default(),
+        prize_mint: Pubkey::default(),
+        prize_amount: args.prize_amount,
+        percentage_to_artist: args.percentage_to_artist,
+        submit_start_at: args.submit_start_at,
+        submit_end_at: args.submit_end_at,
+        vote_start_at: args.vote_start_at,
+        vote_end_at: args.vote_end_at,
+        title_of_contest: args.metadata.title_of_contest.clone(),
+        link_to_project: args.metadata.link_to_project.clone(),
+        description: args.metadata.description.clone(),
+        cover_image_uri: args.metadata.cover_image_uri.clone(),
+        artwork_count: 0,
+        max_artworks: args.max_artworks,
+        required_collection: args.required_collection,
+        max_entries_per_artist: args.max_entries_per_artist,
+        curation_enabled: args.curation_enabled,
+        curators: vec![],
+        judges: args.judges.clone(),
+        judge_weight_percent: args.judge_weight_percent,
+        artist_vesting_duration: args.artist_vesting_duration,
+        voter_reward_mode: args.voter_reward_mode,
+        claim_end_at: args.claim_end_at,
+        tally_pass: 0,
+        tallied_artwork_count: 0,
+        total_vote_count: 0,
+        total_vote_weight: 0,
+        winner_score: 0,
+        winning_vote_total: 0,
+        winning_artwork_ids: vec![],
+        is_finalized: false,
+        artist_payout_per_winner: 0,
+        voter_pool_amount: 0,
+        voter_payout_per_vote: 0,
+        bump: 0,
+        prize_vault_bump: 0,
+        prize_vault_authority_bump: 0,
+        version: Contest::VERSION,
+        status: ContestStatus::Draft,
+        legacy_seeds: false,
+        reserved: [0; 31],
+        _pad: [0; 31],
+    }
+}
+
+fn simulated_artwork(contest: &Contest, artwork_id: u64, tally: &ArtworkTally) -> Artwork {
+    Artwork {
+        is_initialized: true,
+        artwork_id,
+        associated_contest_id: contest.contest_id,
+        artist_key: Pubkey::default(),
+        entry_index: 0,
+        artwork_token_account: Pubkey::default(),
+        vote_count: tally.vote_count,
+        vote_weight_total: tally.vote_weight_total,
+        judge_score_total: tally.judge_score_total,
+        status: if tally.rejected {
+            ArtworkStatus::Rejected
+        } else {
+            ArtworkStatus::Approved
+        },
+        prize_released_amount: 0,
+        bump: 0,
+        nft_vault_bump: 0,
+        nft_vault_authority_bump: 0,
+        version: Artwork::VERSION,
+        legacy_seeds: false,
+        reserved: [0; 32],
+        _pad: [0; 31],
+    }
+}
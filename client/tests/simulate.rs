@@ -0,0 +1,94 @@
+//! Checks the off-chain simulation against the program, on the contest fixture of the program
+//! tests.
+
+#[path = "../../programs/nft_contest/tests/common/mod.rs"]
+mod common;
+
+use common::lifecycle::*;
+use nft_contest::state::{Artwork, Contest, VoteData};
+use nft_contest_client::pda;
+use nft_contest_client::simulate::{ArtworkTally, Simulation};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn simulation_matches_lifecycle_payouts() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+    review_entries(&mut env).await;
+    vote_and_score(&mut env).await;
+
+    let contest: Contest = env.bank.account(&env.contest).await;
+    let mut tallies = vec![];
+    for address in &env.artworks {
+        let artwork: Artwork = env.bank.account(address).await;
+        tallies.push(ArtworkTally::from(&artwork));
+    }
+    let simulation = Simulation::run(&contest, &tallies).unwrap();
+
+    tally_and_finalize(&mut env).await;
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(
+        simulation.winning_artwork_ids(),
+        contest.winning_artwork_ids
+    );
+    assert_eq!(
+        simulation.contest.total_vote_count,
+        contest.total_vote_count
+    );
+    assert_eq!(
+        simulation.contest.total_vote_weight,
+        contest.total_vote_weight
+    );
+    assert_eq!(
+        simulation.contest.artist_payout_per_winner,
+        contest.artist_payout_per_winner
+    );
+    assert_eq!(
+        simulation.contest.voter_pool_amount,
+        contest.voter_pool_amount
+    );
+    assert_eq!(
+        simulation.contest.voter_payout_per_vote,
+        contest.voter_payout_per_vote
+    );
+
+    for entry_index in 0..2 {
+        let before = env.bank.token_balance(&env.artist_prize_account).await;
+        let claim_ix = claim_by_artist_instruction(&env, entry_index);
+        let claimed = env.bank.send(&[claim_ix], &[&env.artist]).await.is_ok();
+        let paid = env.bank.token_balance(&env.artist_prize_account).await - before;
+        assert_eq!(paid, simulation.artist_prize(entry_index as u64));
+        // only winners can claim
+        assert_eq!(claimed, paid > 0);
+    }
+
+    for voter in 0..2 {
+        let vote_data: VoteData = env
+            .bank
+            .account(&pda::find_vote_data_address(&env.contest, &env.voters[voter].pubkey()).0)
+            .await;
+        let before = env
+            .bank
+            .token_balance(&env.voter_prize_accounts[voter])
+            .await;
+        let claim_ix = claim_by_voter_instruction(&env, voter);
+        env.bank
+            .send(&[claim_ix], &[&env.voters[voter]])
+            .await
+            .unwrap();
+        let paid = env
+            .bank
+            .token_balance(&env.voter_prize_accounts[voter])
+            .await
+            - before;
+        assert_eq!(
+            paid,
+            simulation
+                .voter_reward(vote_data.voted_artwork_id, vote_data.weight)
+                .unwrap()
+        );
+        assert!(paid > 0);
+    }
+}
@@ -0,0 +1,450 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::InstructionData;
+use anyhow::{anyhow, Result};
+use nft_contest::instruction;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// distribute takes (vote_data, voter_token_account) pairs after its 5 named accounts
+const DISTRIBUTE_PAIRS_START: usize = 5;
+const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+// spl-token instruction tags
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+
+/// A payout of the prize vault, or of an artwork NFT to the contest owner.
+pub struct Claim {
+    pub signature: String,
+    pub instruction_index: usize,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub kind: &'static str,
+    pub contest: String,
+    pub artwork: Option<String>,
+    pub vote: Option<String>,
+    pub claimant: Option<String>,
+    pub token_account: String,
+    pub mint: String,
+    pub amount: u64,
+}
+
+/// Positions of the accounts of a claim instruction, in the order of its `Accounts` struct.
+struct ClaimAccounts {
+    kind: &'static str,
+    claimant: usize,
+    contest: usize,
+    artwork: Option<usize>,
+    vote: Option<usize>,
+    token_account: usize,
+}
+
+// instruction discriminator, the first 8 bytes of the instruction data
+fn sighash(ix: impl InstructionData) -> Vec<u8> {
+    ix.data()[..8].to_vec()
+}
+
+fn claim_accounts(data: &[u8]) -> Option<ClaimAccounts> {
+    let discriminator = data.get(..8)?;
+    if discriminator == sighash(instruction::ClaimByArtist {}) {
+        Some(ClaimAccounts {
+            kind: "artist",
+            claimant: 0,
+            contest: 2,
+            artwork: Some(1),
+            vote: None,
+            token_account: 6,
+        })
+    } else if discriminator == sighash(instruction::ClaimByVoter {}) {
+        Some(ClaimAccounts {
+            kind: "voter",
+            claimant: 0,
+            contest: 2,
+            artwork: Some(1),
+            vote: Some(4),
+            token_account: 7,
+        })
+    } else if discriminator == sighash(instruction::ClaimByContestOwner {}) {
+        Some(ClaimAccounts {
+            kind: "contest_owner",
+            claimant: 0,
+            contest: 1,
+            artwork: Some(2),
+            vote: None,
+            token_account: 6,
+        })
+    } else if discriminator == sighash(instruction::SweepRemainder {}) {
+        Some(ClaimAccounts {
+            kind: "sweep",
+            claimant: 0,
+            contest: 1,
+            artwork: None,
+            vote: None,
+            token_account: 5,
+        })
+    } else {
+        None
+    }
+}
+
+fn is_distribute(data: &[u8]) -> bool {
+    data.get(..8) == Some(&sighash(instruction::Distribute {})[..])
+}
+
+/// Claims made by the top-level instructions of a transaction returned by getTransaction with the
+/// json encoding, their amount being what the instruction's inner token transfers sent to the
+/// recipient. Failed transactions and payouts transferring nothing are skipped. `voters` maps vote data addresses to their voter, to name the claimant of
+/// distributions.
+pub fn parse_claims(transaction: &Value, voters: &HashMap<Pubkey, Pubkey>) -> Result<Vec<Claim>> {
+    let meta = &transaction["meta"];
+    if !meta["err"].is_null() {
+        return Ok(vec![]);
+    }
+    let signature = transaction["transaction"]["signatures"][0]
+        .as_str()
+        .ok_or_else(|| anyhow!("transaction without signature"))?;
+    let message = &transaction["transaction"]["message"];
+    let mut keys = strings(&message["accountKeys"]);
+    keys.extend(strings(&meta["loadedAddresses"]["writable"]));
+    keys.extend(strings(&meta["loadedAddresses"]["readonly"]));
+    let program_id = nft_contest::ID.to_string();
+
+    let mut claims = vec![];
+    let instructions = message["instructions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    for (instruction_index, ix) in instructions.iter().enumerate() {
+        let program_index = ix["programIdIndex"].as_u64().unwrap_or(u64::MAX) as usize;
+        if keys.get(program_index) != Some(&program_id) {
+            continue;
+        }
+        let data = bs58::decode(ix["data"].as_str().unwrap_or("")).into_vec()?;
+        let account_indexes: Vec<usize> = ix["accounts"]
+            .as_array()
+            .map(|accounts| {
+                accounts
+                    .iter()
+                    .filter_map(|index| index.as_u64())
+                    .map(|index| index as usize)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let key_index = |position: usize| -> Result<usize> {
+            account_indexes
+                .get(position)
+                .copied()
+                .filter(|index| *index < keys.len())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "instruction {} of {} lacks accounts",
+                        instruction_index,
+                        signature
+                    )
+                })
+        };
+        let key = |position: usize| -> Result<String> { Ok(keys[key_index(position)?].clone()) };
+
+        let new_claim = |kind: &'static str, contest: String, token_account_index: usize| {
+            received(meta, &keys, instruction_index, token_account_index).map(|(mint, amount)| {
+                Claim {
+                    signature: signature.to_string(),
+                    instruction_index,
+                    slot: transaction["slot"].as_u64().unwrap_or(0),
+                    block_time: transaction["blockTime"].as_i64(),
+                    kind,
+                    contest,
+                    artwork: None,
+                    vote: None,
+                    claimant: None,
+                    token_account: keys[token_account_index].clone(),
+                    mint,
+                    amount,
+                }
+            })
+        };
+
+        if let Some(accounts) = claim_accounts(&data) {
+            let token_account_index = key_index(accounts.token_account)?;
+            if let Some(mut claim) =
+                new_claim(accounts.kind, key(accounts.contest)?, token_account_index)
+            {
+                claim.artwork = accounts.artwork.map(key).transpose()?;
+                claim.vote = accounts.vote.map(key).transpose()?;
+                claim.claimant = Some(key(accounts.claimant)?);
+                claims.push(claim);
+            }
+        } else if is_distribute(&data) {
+            let contest = key(0)?;
+            let mut position = DISTRIBUTE_PAIRS_START;
+            while position + 1 < account_indexes.len() {
+                let vote = key(position)?;
+                if let Some(mut claim) =
+                    new_claim("distribute", contest.clone(), key_index(position + 1)?)
+                {
+                    claim.claimant = Pubkey::from_str(&vote)
+                        .ok()
+                        .and_then(|vote| voters.get(&vote))
+                        .map(|voter| voter.to_string());
+                    claim.vote = Some(vote);
+                    claims.push(claim);
+                }
+                position += 2;
+            }
+        }
+    }
+    Ok(claims)
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn token_amount(balances: &Value, account_index: usize) -> Option<(String, u64)> {
+    balances.as_array()?.iter().find_map(|balance| {
+        if balance["accountIndex"].as_u64()? as usize != account_index {
+            return None;
+        }
+        let amount = balance["uiTokenAmount"]["amount"].as_str()?.parse().ok()?;
+        Some((balance["mint"].as_str()?.to_string(), amount))
+    })
+}
+
+/// Mint and amount transferred to a token account by the inner spl-token `Transfer` and
+/// `TransferChecked` instructions of a top-level instruction. The mint is read from the token
+/// balances of the transaction.
+fn received(
+    meta: &Value,
+    keys: &[String],
+    instruction_index: usize,
+    account_index: usize,
+) -> Option<(String, u64)> {
+    let inner = meta["innerInstructions"]
+        .as_array()?
+        .iter()
+        .find(|inner| inner["index"].as_u64() == Some(instruction_index as u64))?;
+    let mut amount = 0u64;
+    for ix in inner["instructions"].as_array()? {
+        let program_index = ix["programIdIndex"].as_u64()? as usize;
+        if keys.get(program_index).map(String::as_str) != Some(TOKEN_PROGRAM_ID) {
+            continue;
+        }
+        let data = bs58::decode(ix["data"].as_str()?).into_vec().ok()?;
+        let destination_position = match data.first() {
+            Some(&TRANSFER) => 1,
+            Some(&TRANSFER_CHECKED) => 2,
+            _ => continue,
+        };
+        if ix["accounts"][destination_position].as_u64() != Some(account_index as u64) {
+            continue;
+        }
+        let transferred = u64::from_le_bytes(data.get(1..9)?.try_into().ok()?);
+        amount = amount.saturating_add(transferred);
+    }
+    if amount == 0 {
+        return None;
+    }
+    let (mint, _) = token_amount(&meta["postTokenBalances"], account_index)
+        .or_else(|| token_amount(&meta["preTokenBalances"], account_index))?;
+    Some((mint, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use nft_contest_client::{instructions, pda};
+    use serde_json::json;
+
+    /// A successful transaction made of `ix` alone, whose inner instructions send each amount to
+    /// its destination with `Transfer`, or `TransferChecked` if `checked`.
+    fn transaction(ix: &Instruction, transfers: &[(Pubkey, u64)], checked: bool) -> Value {
+        let mut keys: Vec<String> = ix
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey.to_string())
+            .collect();
+        keys.push(ix.program_id.to_string());
+        keys.push(TOKEN_PROGRAM_ID.to_string());
+        let index = |key: &Pubkey| keys.iter().position(|k| *k == key.to_string()).unwrap();
+        let mint = Pubkey::new_unique();
+        let mut inner = vec![];
+        let mut balances = vec![];
+        for (destination, amount) in transfers {
+            let (tag, accounts) = if checked {
+                (
+                    TRANSFER_CHECKED,
+                    vec![0, keys.len() - 1, index(destination), 0],
+                )
+            } else {
+                (TRANSFER, vec![0, index(destination), 0])
+            };
+            let mut data = vec![tag];
+            data.extend(amount.to_le_bytes());
+            if checked {
+                data.push(6);
+            }
+            inner.push(json!({
+                "programIdIndex": keys.len() - 1,
+                "accounts": accounts,
+                "data": bs58::encode(data).into_string(),
+            }));
+            // the transaction wide balance change also counts unrelated transfers
+            balances.push(json!({
+                "accountIndex": index(destination),
+                "mint": mint.to_string(),
+                "uiTokenAmount": { "amount": (amount + 1_000).to_string() },
+            }));
+        }
+        json!({
+            "slot": 7,
+            "blockTime": null,
+            "meta": {
+                "err": null,
+                "innerInstructions": [{ "index": 0, "instructions": inner }],
+                "preTokenBalances": [],
+                "postTokenBalances": balances,
+            },
+            "transaction": {
+                "signatures": ["signature"],
+                "message": {
+                    "accountKeys": keys,
+                    "instructions": [{
+                        "programIdIndex": keys.len() - 2,
+                        "accounts": (0..ix.accounts.len()).collect::<Vec<_>>(),
+                        "data": bs58::encode(&ix.data).into_string(),
+                    }],
+                },
+            },
+        })
+    }
+
+    fn parse_one(ix: &Instruction, recipient: &Pubkey, amount: u64, checked: bool) -> Claim {
+        let transaction = transaction(ix, &[(*recipient, amount)], checked);
+        let mut claims = parse_claims(&transaction, &HashMap::new()).unwrap();
+        assert_eq!(claims.len(), 1);
+        claims.pop().unwrap()
+    }
+
+    #[test]
+    fn decodes_claim_by_artist() {
+        let (artist, contest, mint, token_account) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = instructions::claim_by_artist(&artist, &contest, 1, &mint, &token_account);
+        let claim = parse_one(&ix, &token_account, 500, false);
+        assert_eq!(claim.kind, "artist");
+        assert_eq!(claim.claimant, Some(artist.to_string()));
+        assert_eq!(claim.contest, contest.to_string());
+        let artwork = pda::find_artwork_address(&contest, &artist, 1).0;
+        assert_eq!(claim.artwork, Some(artwork.to_string()));
+        assert_eq!(claim.vote, None);
+        assert_eq!(claim.token_account, token_account.to_string());
+        assert_eq!(claim.amount, 500);
+    }
+
+    #[test]
+    fn decodes_claim_by_voter() {
+        let (voter, contest, artwork, mint, token_account) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = instructions::claim_by_voter(&voter, &contest, &artwork, &mint, &token_account);
+        let claim = parse_one(&ix, &token_account, 42, true);
+        assert_eq!(claim.kind, "voter");
+        assert_eq!(claim.claimant, Some(voter.to_string()));
+        assert_eq!(claim.contest, contest.to_string());
+        assert_eq!(claim.artwork, Some(artwork.to_string()));
+        let vote = pda::find_vote_data_address(&contest, &voter).0;
+        assert_eq!(claim.vote, Some(vote.to_string()));
+        assert_eq!(claim.token_account, token_account.to_string());
+        assert_eq!(claim.amount, 42);
+    }
+
+    #[test]
+    fn decodes_claim_by_contest_owner() {
+        let (owner, contest, artist, nft_mint, token_account) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = instructions::claim_by_contest_owner(
+            &owner,
+            &contest,
+            &artist,
+            0,
+            &nft_mint,
+            &token_account,
+        );
+        let claim = parse_one(&ix, &token_account, 1, false);
+        assert_eq!(claim.kind, "contest_owner");
+        assert_eq!(claim.claimant, Some(owner.to_string()));
+        assert_eq!(claim.contest, contest.to_string());
+        let artwork = pda::find_artwork_address(&contest, &artist, 0).0;
+        assert_eq!(claim.artwork, Some(artwork.to_string()));
+        assert_eq!(claim.token_account, token_account.to_string());
+        assert_eq!(claim.amount, 1);
+    }
+
+    #[test]
+    fn decodes_sweep_remainder() {
+        let (owner, contest, mint, destination) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let ix = instructions::sweep_remainder(&owner, &contest, &mint, &destination);
+        let claim = parse_one(&ix, &destination, 3, false);
+        assert_eq!(claim.kind, "sweep");
+        assert_eq!(claim.claimant, Some(owner.to_string()));
+        assert_eq!(claim.contest, contest.to_string());
+        assert_eq!(claim.artwork, None);
+        assert_eq!(claim.token_account, destination.to_string());
+        assert_eq!(claim.amount, 3);
+    }
+
+    #[test]
+    fn decodes_each_payout_of_distribute() {
+        let (contest, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let voters = [
+            (Pubkey::new_unique(), Pubkey::new_unique()),
+            (Pubkey::new_unique(), Pubkey::new_unique()),
+        ];
+        let ix = instructions::distribute(&contest, &mint, &voters);
+        let transaction = transaction(&ix, &[(voters[0].1, 10), (voters[1].1, 20)], false);
+        let vote_addresses: HashMap<Pubkey, Pubkey> = voters
+            .iter()
+            .map(|(voter, _)| (pda::find_vote_data_address(&contest, voter).0, *voter))
+            .collect();
+        let claims = parse_claims(&transaction, &vote_addresses).unwrap();
+        assert_eq!(claims.len(), 2);
+        for ((voter, token_account), (claim, amount)) in
+            voters.iter().zip(claims.iter().zip([10, 20]))
+        {
+            assert_eq!(claim.kind, "distribute");
+            assert_eq!(claim.contest, contest.to_string());
+            assert_eq!(claim.claimant, Some(voter.to_string()));
+            let vote = pda::find_vote_data_address(&contest, voter).0;
+            assert_eq!(claim.vote, Some(vote.to_string()));
+            assert_eq!(claim.token_account, token_account.to_string());
+            assert_eq!(claim.amount, amount);
+        }
+    }
+}
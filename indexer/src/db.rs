@@ -0,0 +1,276 @@
+use crate::claims::Claim;
+use crate::snapshot::Snapshot;
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS contests (
+    address TEXT PRIMARY KEY,
+    contest_id INTEGER NOT NULL,
+    owner TEXT NOT NULL,
+    status TEXT NOT NULL,
+    title TEXT NOT NULL,
+    prize_mint TEXT NOT NULL,
+    prize_amount INTEGER,
+    percentage_to_artist INTEGER NOT NULL,
+    voter_reward_mode TEXT NOT NULL,
+    submit_start_at INTEGER,
+    submit_end_at INTEGER,
+    vote_start_at INTEGER,
+    vote_end_at INTEGER,
+    claim_end_at INTEGER,
+    artwork_count INTEGER,
+    total_vote_count INTEGER,
+    is_finalized INTEGER NOT NULL,
+    winning_artwork_ids TEXT NOT NULL,
+    artist_payout_per_winner INTEGER,
+    voter_payout_per_vote INTEGER
+);
+CREATE TABLE IF NOT EXISTS artworks (
+    address TEXT PRIMARY KEY,
+    contest TEXT,
+    contest_id INTEGER NOT NULL,
+    artwork_id INTEGER NOT NULL,
+    artist TEXT NOT NULL,
+    entry_index INTEGER NOT NULL,
+    token_account TEXT NOT NULL,
+    status TEXT NOT NULL,
+    vote_count INTEGER,
+    vote_weight_total INTEGER,
+    judge_score_total INTEGER,
+    prize_released_amount INTEGER
+);
+CREATE INDEX IF NOT EXISTS artworks_by_contest ON artworks (contest);
+CREATE TABLE IF NOT EXISTS votes (
+    address TEXT PRIMARY KEY,
+    contest TEXT NOT NULL,
+    voter TEXT NOT NULL,
+    artwork_id INTEGER NOT NULL,
+    weight INTEGER,
+    reward_claimed INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS votes_by_contest ON votes (contest);
+CREATE TABLE IF NOT EXISTS claims (
+    signature TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    kind TEXT NOT NULL,
+    contest TEXT NOT NULL,
+    artwork TEXT,
+    vote TEXT,
+    claimant TEXT,
+    token_account TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    amount INTEGER,
+    PRIMARY KEY (signature, instruction_index, token_account)
+);
+CREATE INDEX IF NOT EXISTS claims_by_contest ON claims (contest);
+CREATE TABLE IF NOT EXISTS sync_state (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+const TABLES: [&str; 5] = ["contests", "artworks", "votes", "claims", "sync_state"];
+
+// newest transaction signature indexed from the node
+const LAST_SIGNATURE_KEY: &str = "last_signature";
+
+/// SQLite integers are signed, amounts and timestamps above `i64::MAX` are stored as NULL, like the
+/// `u64::MAX` claim deadline of contests migrated from the first release.
+fn integer(value: u64) -> Option<i64> {
+    i64::try_from(value).ok()
+}
+
+pub struct Db {
+    connection: Connection,
+}
+
+impl Db {
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Drops every table, so that the next sync indexes all transactions again.
+    pub fn reset(&mut self) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        for table in TABLES {
+            transaction.execute_batch(&format!("DROP TABLE IF EXISTS {};", table))?;
+        }
+        transaction.execute_batch(SCHEMA)?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    pub fn last_signature(&self) -> Result<Option<String>> {
+        Ok(self
+            .connection
+            .query_row(
+                "SELECT value FROM sync_state WHERE key = ?1",
+                [LAST_SIGNATURE_KEY],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    /// Replaces the accounts with `snapshot` and adds `claims`, in one transaction. Claims already
+    /// indexed are ignored. Contest statuses are the ones at `now_ts`. Returns the number of new
+    /// claims.
+    pub fn sync(
+        &mut self,
+        snapshot: &Snapshot,
+        claims: &[Claim],
+        last_signature: Option<&str>,
+        now_ts: u64,
+    ) -> Result<usize> {
+        let transaction = self.connection.transaction()?;
+        transaction
+            .execute_batch("DELETE FROM contests; DELETE FROM artworks; DELETE FROM votes;")?;
+
+        let contest_addresses = snapshot.contest_addresses();
+        for (address, contest) in &snapshot.contests {
+            transaction.execute(
+                "INSERT INTO contests VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10,
+                    ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20
+                )",
+                params![
+                    address.to_string(),
+                    contest.contest_id,
+                    contest.contest_owner.to_string(),
+                    format!("{:?}", contest.current_status(now_ts)),
+                    contest.title_of_contest,
+                    contest.prize_mint.to_string(),
+                    integer(contest.prize_amount),
+                    contest.percentage_to_artist,
+                    format!("{:?}", contest.voter_reward_mode),
+                    integer(contest.submit_start_at),
+                    integer(contest.submit_end_at),
+                    integer(contest.vote_start_at),
+                    integer(contest.vote_end_at),
+                    integer(contest.claim_end_at),
+                    integer(contest.artwork_count),
+                    integer(contest.total_vote_count),
+                    contest.is_finalized,
+                    format!("{:?}", contest.winning_artwork_ids),
+                    integer(contest.artist_payout_per_winner),
+                    integer(contest.voter_payout_per_vote),
+                ],
+            )?;
+        }
+        for (address, artwork) in &snapshot.artworks {
+            transaction.execute(
+                "INSERT INTO artworks VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![
+                    address.to_string(),
+                    contest_addresses
+                        .get(&artwork.associated_contest_id)
+                        .map(|contest| contest.to_string()),
+                    artwork.associated_contest_id,
+                    artwork.artwork_id,
+                    artwork.artist_key.to_string(),
+                    artwork.entry_index,
+                    artwork.artwork_token_account.to_string(),
+                    format!("{:?}", artwork.status),
+                    integer(artwork.vote_count),
+                    integer(artwork.vote_weight_total),
+                    integer(artwork.judge_score_total),
+                    integer(artwork.prize_released_amount),
+                ],
+            )?;
+        }
+        for (address, vote) in &snapshot.votes {
+            transaction.execute(
+                "INSERT INTO votes VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    address.to_string(),
+                    vote.contest.to_string(),
+                    vote.voter_key.to_string(),
+                    vote.voted_artwork_id,
+                    integer(vote.weight),
+                    vote.reward_claimed,
+                ],
+            )?;
+        }
+
+        let mut inserted = 0;
+        for claim in claims {
+            inserted += transaction.execute(
+                "INSERT OR IGNORE INTO claims VALUES (
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12
+                )",
+                params![
+                    claim.signature,
+                    claim.instruction_index,
+                    claim.slot,
+                    claim.block_time,
+                    claim.kind,
+                    claim.contest,
+                    claim.artwork,
+                    claim.vote,
+                    claim.claimant,
+                    claim.token_account,
+                    claim.mint,
+                    integer(claim.amount),
+                ],
+            )?;
+        }
+
+        if let Some(signature) = last_signature {
+            transaction.execute(
+                "INSERT OR REPLACE INTO sync_state VALUES (?1, ?2)",
+                [LAST_SIGNATURE_KEY, signature],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(inserted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use nft_contest::state::legacy::ContestV0;
+
+    #[test]
+    fn stores_migrated_contests_without_claim_deadline() {
+        let contest = ContestV0 {
+            is_initialized: true,
+            contest_id: 7,
+            contest_owner: Pubkey::new_unique(),
+            prize_amount: 1_000,
+            percentage_to_artist: 70,
+            submit_start_at: 10,
+            submit_end_at: 20,
+            vote_start_at: 30,
+            vote_end_at: 40,
+            title_of_contest: b"legacy".to_vec(),
+            link_to_project: vec![],
+            artwork_count: 0,
+            artworks_vote_counter: vec![],
+        };
+        let snapshot = Snapshot {
+            contests: vec![(Pubkey::new_unique(), contest.into())],
+            ..Snapshot::default()
+        };
+
+        let mut db = Db::open(Path::new(":memory:")).unwrap();
+        db.sync(&snapshot, &[], None, 50).unwrap();
+
+        let (claim_end_at, prize_amount): (Option<i64>, Option<i64>) = db
+            .connection
+            .query_row(
+                "SELECT claim_end_at, prize_amount FROM contests WHERE contest_id = 7",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(claim_end_at, None);
+        assert_eq!(prize_amount, Some(1_000));
+    }
+}
@@ -0,0 +1,96 @@
+//! Indexer writing the contests, artworks, votes and claims of the nft_contest program into
+//! SQLite, from an RPC node, e.g. a local test validator, or from a JSON dump.
+//!
+//! Accounts are replaced by the current snapshot on every run, while claims are read from the
+//! transactions of the program, from the newest one indexed so far. A dump has the shape
+//! `{ "accounts": [getProgramAccounts entries], "transactions": [getTransaction results] }`, with
+//! base64 account data and json encoded transactions.
+
+mod claims;
+mod db;
+mod snapshot;
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use db::Db;
+use nft_contest_client::rpc::RpcClient;
+use serde_json::Value;
+use snapshot::Snapshot;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+#[clap(name = "nft-contest-indexer", version, about)]
+struct Cli {
+    /// JSON RPC URL of the cluster
+    #[clap(long, short = 'u', default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Index a JSON dump instead of querying the cluster
+    #[clap(long)]
+    dump: Option<PathBuf>,
+    /// SQLite database to write
+    #[clap(long, default_value = "nft_contest.sqlite")]
+    db: PathBuf,
+    /// Drop every table and rebuild the database from scratch
+    #[clap(long)]
+    resync: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut db = Db::open(&cli.db)?;
+    if cli.resync {
+        db.reset()?;
+    }
+
+    let (accounts, transactions, last_signature) = match &cli.dump {
+        Some(path) => {
+            let dump: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            (
+                array(&dump["accounts"])?,
+                array(&dump["transactions"])?,
+                None,
+            )
+        }
+        None => {
+            let rpc = RpcClient::new(&cli.url);
+            let program_id = nft_contest::ID;
+            let accounts = rpc.get_keyed_program_accounts(&program_id, &[])?;
+            let signatures =
+                rpc.get_signatures_since(&program_id, db.last_signature()?.as_deref())?;
+            let transactions = signatures
+                .iter()
+                .map(|signature| rpc.get_transaction(signature))
+                .collect::<Result<Vec<_>>>()?;
+            (accounts, transactions, signatures.last().cloned())
+        }
+    };
+
+    let snapshot = Snapshot::from_keyed_accounts(&accounts)?;
+    let voters = snapshot.voters();
+    let mut claims = vec![];
+    for transaction in &transactions {
+        claims.extend(claims::parse_claims(transaction, &voters)?);
+    }
+    // contest statuses move with the schedule, they are indexed as of now
+    let now_ts = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let inserted = db.sync(&snapshot, &claims, last_signature.as_deref(), now_ts)?;
+
+    println!(
+        "Indexed {} contests, {} artworks, {} votes and {} new claims from {} transactions",
+        snapshot.contests.len(),
+        snapshot.artworks.len(),
+        snapshot.votes.len(),
+        inserted,
+        transactions.len()
+    );
+    Ok(())
+}
+
+fn array(value: &Value) -> Result<Vec<Value>> {
+    match value {
+        Value::Array(values) => Ok(values.clone()),
+        Value::Null => Ok(vec![]),
+        _ => Err(anyhow!("expected an array in the dump")),
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, Result};
+use nft_contest_client::accounts::{self, Artwork, Contest, VoteData};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Contests, artworks and votes among the accounts of the program, other accounts are skipped.
+#[derive(Default)]
+pub struct Snapshot {
+    pub contests: Vec<(Pubkey, Contest)>,
+    pub artworks: Vec<(Pubkey, Artwork)>,
+    pub votes: Vec<(Pubkey, VoteData)>,
+}
+
+impl Snapshot {
+    /// Decodes getProgramAccounts entries, `{ "pubkey", "account": { "data": [base64, ..] } }`.
+    pub fn from_keyed_accounts(keyed_accounts: &[Value]) -> Result<Self> {
+        let mut snapshot = Snapshot::default();
+        for keyed_account in keyed_accounts {
+            let address = Pubkey::from_str(
+                keyed_account["pubkey"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("account without pubkey"))?,
+            )?;
+            let encoded = keyed_account["account"]["data"][0]
+                .as_str()
+                .ok_or_else(|| anyhow!("account {} is not base64 encoded", address))?;
+            let data = base64::decode(encoded)?;
+            if data.len() < 8 {
+                continue;
+            }
+            let discriminator = &data[..8];
+            if discriminator == Contest::discriminator() {
+                snapshot
+                    .contests
+                    .push((address, accounts::decode_contest(&data)?));
+            } else if discriminator == Artwork::discriminator() {
+                snapshot
+                    .artworks
+                    .push((address, accounts::decode_artwork(&data)?));
+            } else if discriminator == VoteData::discriminator() {
+                snapshot
+                    .votes
+                    .push((address, accounts::decode_vote_data(&data)?));
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Addresses of the contests by contest id.
+    pub fn contest_addresses(&self) -> HashMap<u64, Pubkey> {
+        self.contests
+            .iter()
+            .map(|(address, contest)| (contest.contest_id, *address))
+            .collect()
+    }
+
+    /// Voters by vote data address.
+    pub fn voters(&self) -> HashMap<Pubkey, Pubkey> {
+        self.votes
+            .iter()
+            .map(|(address, vote)| (*address, vote.voter_key))
+            .collect()
+    }
+}
@@ -8,4 +8,130 @@ pub enum ErrorCode {
     Launch,
     Submit,
     Vote,
+    #[msg("Title of contest must not be empty")]
+    EmptyTitle,
+    #[msg("Title of contest is too long")]
+    TitleTooLong,
+    #[msg("Link to project is too long")]
+    LinkTooLong,
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    #[msg("Cover image uri is too long")]
+    CoverImageUriTooLong,
+    #[msg("Contest metadata can not be updated once voting has started")]
+    MetadataLocked,
+    #[msg("Max artworks must be greater than zero")]
+    InvalidMaxArtworks,
+    #[msg("Contest has reached its max number of artworks")]
+    ContestFull,
+    #[msg("New max artworks must be greater than the current one")]
+    CapacityNotIncreased,
+    #[msg("Voting is not open for this contest")]
+    NotInVotingPeriod,
+    #[msg("Voting has not ended yet")]
+    VotingNotEnded,
+    #[msg("Artwork does not belong to this contest")]
+    ArtworkContestMismatch,
+    #[msg("Artworks must be tallied in order of artwork id")]
+    ArtworkOutOfOrder,
+    #[msg("Votes have not been tallied for every artwork")]
+    VotesNotTallied,
+    #[msg("Contest is already finalized")]
+    ContestAlreadyFinalized,
+    #[msg("Contest is not finalized yet")]
+    ContestNotFinalized,
+    #[msg("Artwork is not a winner of this contest")]
+    NotWinningArtwork,
+    #[msg("Token is not an NFT: supply must be 1 with no mint authority")]
+    NotAnNft,
+    #[msg("Metaplex metadata of the NFT is missing or invalid")]
+    InvalidNftMetadata,
+    #[msg("Artist is not a verified creator of the NFT")]
+    ArtistNotVerifiedCreator,
+    #[msg("NFT is not a verified member of the collection required by the contest")]
+    CollectionMismatch,
+    #[msg("Max entries per artist must be greater than zero")]
+    InvalidMaxEntriesPerArtist,
+    #[msg("Artist has reached the max number of entries for this contest")]
+    TooManyEntries,
+    #[msg("Too many curators")]
+    TooManyCurators,
+    #[msg("Curators can not be changed once voting has started")]
+    CuratorsLocked,
+    #[msg("Signer is neither the contest owner nor a curator")]
+    NotReviewer,
+    #[msg("Artworks can only be reviewed between the end of submission and the start of voting")]
+    NotInReviewPeriod,
+    #[msg("Artwork has already been reviewed")]
+    ArtworkAlreadyReviewed,
+    #[msg("Artwork is not approved")]
+    ArtworkNotApproved,
+    #[msg("Curation requires submission to end before voting starts")]
+    NoReviewPeriod,
+    #[msg("Too many judges")]
+    TooManyJudges,
+    #[msg("Judge weight must be at most 100 and requires at least one judge")]
+    InvalidJudgeWeight,
+    #[msg("Signer is not a judge of this contest")]
+    NotJudge,
+    #[msg("Score is above the max judge score")]
+    InvalidScore,
+    #[msg("Artworks can only be scored after submission ends and until voting ends")]
+    NotInJudgingPeriod,
+    #[msg("Nothing to claim yet")]
+    NothingToClaim,
+    #[msg("Contests without voter rewards must give 100 percent to artists")]
+    InvalidVoterRewardMode,
+    #[msg("Voter reward has already been claimed")]
+    RewardAlreadyClaimed,
+    #[msg("Token account does not hold the prize token of this contest")]
+    PrizeMintMismatch,
+    #[msg("Claim deadline must leave time to claim after voting and vesting end")]
+    InvalidClaimDeadline,
+    #[msg("Claim period has ended")]
+    ClaimPeriodEnded,
+    #[msg("Claim period has not ended yet")]
+    ClaimPeriodNotEnded,
+    #[msg("Remaining accounts must be pairs of vote data and destination token account")]
+    InvalidRemainingAccounts,
+    #[msg("Vote data does not belong to this contest")]
+    VoteDataMismatch,
+    #[msg("Destination token account is not the voter's prize token account")]
+    InvalidRewardDestination,
+    #[msg("Payout calculation overflowed")]
+    PayoutOverflow,
+    #[msg("Payout calculation divided by zero")]
+    PayoutDivisionByZero,
+    #[msg("Percentage must not exceed 100")]
+    InvalidPercentage,
+    #[msg("Payout weight exceeds the total weight")]
+    InvalidPayoutWeight,
+    #[msg("Signer is not the upgrade authority of the program")]
+    NotProgramAdmin,
+    #[msg("Account already has the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Account is not of the expected type")]
+    InvalidLegacyAccount,
+    #[msg("Contest status does not allow this operation")]
+    InvalidContestStatus,
+    #[msg("Contest can not be cancelled once artworks are submitted")]
+    ContestHasArtworks,
+    #[msg("Submission period has ended")]
+    SubmissionClosed,
+    #[msg("Contest schedule is out of order")]
+    InvalidSchedule,
+    #[msg("Prize token account does not hold the prize amount")]
+    InsufficientPrize,
+    #[msg("Artworks can only be withdrawn once the review period is over")]
+    ReviewNotEnded,
+    #[msg("Artworks must be migrated before their contest")]
+    ContestAlreadyMigrated,
+    #[msg("Voted artwork id does not match the artwork account")]
+    VotedArtworkMismatch,
+    #[msg("Signer is not the voter of this vote data")]
+    NotVoter,
+    #[msg("Signer is not the artist of this artwork")]
+    NotArtist,
+    #[msg("Every artwork of the contest must be migrated before it")]
+    ArtworksNotMigrated,
 }
@@ -0,0 +1,50 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveArtwork<'info> {
+    pub reviewer: Signer<'info>,
+    #[account(owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+    #[account(mut,
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
+    pub artwork: Account<'info, Artwork>,
+}
+
+pub fn handler(ctx: Context<ApproveArtwork>) -> Result<()> {
+    check_review(
+        &ctx.accounts.contest,
+        &ctx.accounts.artwork,
+        &ctx.accounts.reviewer.key(),
+    )?;
+
+    ctx.accounts.artwork.status = ArtworkStatus::Approved;
+
+    Ok(())
+}
+
+/// Checks shared by approve_artwork and reject_artwork.
+pub fn check_review(contest: &Contest, artwork: &Artwork, reviewer: &Pubkey) -> Result<()> {
+    require!(contest.is_reviewer(reviewer), ErrorCode::NotReviewer);
+
+    // review happens after submission closed and before voting opens
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        contest.submit_end_at < now_ts && now_ts < contest.vote_start_at,
+        ErrorCode::NotInReviewPeriod
+    );
+    contest.require_status(now_ts, &[ContestStatus::Open])?;
+    require!(
+        artwork.status == ArtworkStatus::Pending,
+        ErrorCode::ArtworkAlreadyReviewed
+    );
+
+    Ok(())
+}
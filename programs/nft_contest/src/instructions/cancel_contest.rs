@@ -0,0 +1,106 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct CancelContest<'info> {
+    #[account(mut)]
+    pub contest_owner: Signer<'info>,
+    #[account(mut,
+        owner = *program_id,
+        has_one = contest_owner,
+        has_one = prize_mint,
+        seeds = [CONTEST_SEED,
+            contest_owner.key().as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
+    pub contest: Account<'info, Contest>,
+    pub prize_mint: Account<'info, Mint>,
+    #[account(mut,
+        seeds = [PRIZE_VAULT_SEED,
+            contest.prize_vault_seeds(&contest.key())[0].as_ref(),
+            contest.prize_vault_seeds(&contest.key())[1].as_ref()],
+        bump = contest.prize_vault_bump,
+        token::mint = prize_mint,
+        token::authority = prize_vault_authority)]
+    pub prize_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump = contest.prize_vault_authority_bump)]
+    /// CHECK: only used as a signing PDA
+    pub prize_vault_authority: UncheckedAccount<'info>,
+    #[account(mut,
+        token::mint = prize_mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Contest owner only, before voting starts and while no artwork is submitted, so that no NFT is
+/// left in a vault. Refunds the prize to the destination token account and closes the vault.
+pub fn handler(ctx: Context<CancelContest>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    ctx.accounts
+        .contest
+        .require_status(now_ts, &[ContestStatus::Draft, ContestStatus::Open])?;
+    require!(
+        ctx.accounts.contest.artwork_count == 0,
+        ErrorCode::ContestHasArtworks
+    );
+    ctx.accounts.contest.status = ContestStatus::Cancelled;
+
+    let contest_key = ctx.accounts.contest.key();
+    let authority_seeds = &[
+        PRIZE_VAULT_AUTHORITY_SEED,
+        contest_key.as_ref(),
+        &[ctx.accounts.contest.prize_vault_authority_bump],
+    ];
+
+    // a token account can only be closed once it is empty
+    let prize = ctx.accounts.prize_vault_account.amount;
+    if prize > 0 {
+        token::transfer(
+            ctx.accounts
+                .into_transfer_to_destination_context()
+                .with_signer(&[&authority_seeds[..]]),
+            prize,
+        )?;
+    }
+    token::close_account(
+        ctx.accounts
+            .into_close_vault_context()
+            .with_signer(&[&authority_seeds[..]]),
+    )?;
+
+    Ok(())
+}
+
+impl<'info> CancelContest<'info> {
+    fn into_transfer_to_destination_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.prize_vault_account.to_account_info().clone(),
+            to: self.destination_token_account.to_account_info().clone(),
+            authority: self.prize_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+
+    fn into_close_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.prize_vault_account.to_account_info().clone(),
+            destination: self.contest_owner.to_account_info().clone(),
+            authority: self.prize_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -1,3 +1,5 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
@@ -8,18 +10,34 @@ pub struct ClaimByArtist<'info> {
     #[account(mut)]
     pub artist: Signer<'info>,
     #[account(mut,
-        owner = *program_id)]
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artist.key().as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
     pub artwork: Account<'info, Artwork>,
     #[account(mut,
-        owner = *program_id)]
+        owner = *program_id,
+        seeds = [CONTEST_SEED,
+            contest.contest_owner.as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
     pub contest: Account<'info, Contest>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub prize_mint: Account<'info, Mint>,
     #[account(mut,
+        seeds = [PRIZE_VAULT_SEED,
+            contest.prize_vault_seeds(&contest.key())[0].as_ref(),
+            contest.prize_vault_seeds(&contest.key())[1].as_ref()],
+        bump = contest.prize_vault_bump,
         token::mint = prize_mint,
         token::authority = prize_vault_authority)]
     pub prize_vault_account: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump = contest.prize_vault_authority_bump)]
     /// CHECK: only used as a signing PDA
     pub prize_vault_authority: UncheckedAccount<'info>,
     #[account(mut,
@@ -39,41 +57,48 @@ pub fn handler(ctx: Context<ClaimByArtist>) -> Result<()> {
     // assert!(vote_end_at < now_ts); // to study: is this safe? safe way to compare?
 
     // check if signer is an artist of artwork specified in accounts
-    assert!(ctx.accounts.artwork.artist_key == ctx.accounts.artist.key());
-
-    // fetch the order and num of votes for the most voted artwork_vote_counter in contest account
-    let artworks = &contest.artworks_vote_counter;
-    let mut winner_artwork_num_of_votes = 0 as u64;
-    let mut winner_artwork_id = 0;
-    for i in 0..artworks.len() as usize {
-        if artworks[i] > winner_artwork_num_of_votes {
-            winner_artwork_num_of_votes = artworks[i];
-            winner_artwork_id = i as u64;
-        }
-    }
-
-    let artwork_id = ctx.accounts.artwork.artwork_id;
+    require_keys_eq!(
+        ctx.accounts.artwork.artist_key,
+        ctx.accounts.artist.key(),
+        ErrorCode::NotArtist
+    );
 
-    // check if the specified artwork account is the most voted artwork's account through evaluating artwork id
-    assert!(artwork_id == winner_artwork_id);
+    // winners and payouts are recorded by finalize_contest
+    require!(contest.is_finalized, ErrorCode::ContestNotFinalized);
+    // the prize vault may be swept once the claim period is over
+    require!(
+        Clock::get().unwrap().unix_timestamp as u64 <= contest.claim_end_at,
+        ErrorCode::ClaimPeriodEnded
+    );
 
-    // transfer tokens to vault account
-    let (_prize_vault_authority, prize_vault_authority_bump) = Pubkey::find_program_address(
-        &[
-            b"prize_vault_authority",
-            ctx.accounts.contest.key().as_ref(),
-        ],
-        ctx.program_id,
+    // check if the specified artwork account is a winning artwork
+    require!(
+        ctx.accounts.artwork.associated_contest_id == contest.contest_id,
+        ErrorCode::ArtworkContestMismatch
     );
+    require!(
+        contest.is_winner(ctx.accounts.artwork.artwork_id),
+        ErrorCode::NotWinningArtwork
+    );
+
+    // transfer tokens to artist's token account, signing with the stored bump
     let contest_key = contest.key();
     let authority_seeds = &[
-        b"prize_vault_authority".as_ref(),
+        PRIZE_VAULT_AUTHORITY_SEED,
         contest_key.as_ref(),
-        &[prize_vault_authority_bump],
+        &[contest.prize_vault_authority_bump],
     ];
-    let prize_amount = ctx.accounts.contest.prize_amount;
-    let percentage_to_artist = ctx.accounts.contest.percentage_to_artist;
-    let claimed_amount = (prize_amount * percentage_to_artist as u64).safe_div(100)?;
+    // pay whatever has vested since the last claim
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    let claimed_amount = contest
+        .artist_vested_amount(now_ts)?
+        .safe_sub(ctx.accounts.artwork.prize_released_amount)?;
+    require!(claimed_amount > 0, ErrorCode::NothingToClaim);
+    ctx.accounts.artwork.prize_released_amount = ctx
+        .accounts
+        .artwork
+        .prize_released_amount
+        .safe_add(claimed_amount)?;
     token::transfer(
         ctx.accounts
             .into_transfer_to_pda_context()
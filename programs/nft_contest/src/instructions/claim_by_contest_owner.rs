@@ -1,3 +1,5 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
@@ -7,17 +9,37 @@ pub struct ClaimByContestOwner<'info> {
     #[account(mut)]
     pub contest_owner: Signer<'info>,
     #[account(mut,
-    owner = *program_id)]
+    owner = *program_id,
+    has_one = contest_owner,
+    seeds = [CONTEST_SEED,
+        contest_owner.key().as_ref(),
+        contest.id_seed().as_ref()],
+    bump = contest.bump)]
     pub contest: Account<'info, Contest>,
     #[account(mut,
-      owner = *program_id)]
+      owner = *program_id,
+      seeds = [ARTWORK_SEED,
+        contest.key().as_ref(),
+        artwork.artist_key.as_ref(),
+        artwork.entry_seed().as_ref()],
+      bump = artwork.bump)]
     pub artwork: Account<'info, Artwork>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub nft_mint: Account<'info, Mint>,
     #[account(mut,
+    seeds = [NFT_VAULT_SEED,
+        contest.key().as_ref(),
+        artwork.artist_key.as_ref(),
+        artwork.entry_seed().as_ref()],
+    bump = artwork.nft_vault_bump,
     token::mint = nft_mint,
     token::authority = nft_vault_authority)]
     pub nft_vault_account: Account<'info, TokenAccount>,
+    #[account(
+    seeds = [NFT_VAULT_AUTHORITY_SEED,
+        contest.key().as_ref(),
+        artwork.artist_key.as_ref()],
+    bump = artwork.nft_vault_authority_bump)]
     /// CHECK: only used as a signing PDA
     pub nft_vault_authority: UncheckedAccount<'info>,
     #[account(mut,
@@ -34,31 +56,34 @@ pub fn handler(ctx: Context<ClaimByContestOwner>) -> Result<()> {
     let artwork = &ctx.accounts.artwork;
 
     // check if Signer is the owner of the contest which the claimed NFT is submitted to
-    assert!(artwork.associated_contest_id == ctx.accounts.contest.contest_id); // artwork corresponds to contest
+    require!(
+        artwork.associated_contest_id == ctx.accounts.contest.contest_id,
+        ErrorCode::ArtworkContestMismatch
+    );
+
+    // submitted NFTs stay in their vaults until the result is final
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    contest.require_status(now_ts, &[ContestStatus::Finalized, ContestStatus::Settled])?;
+    // unreviewed entries are left for their artist to withdraw
+    require!(
+        artwork.status == ArtworkStatus::Approved,
+        ErrorCode::ArtworkNotApproved
+    );
 
-    // contest_owner = signer is assured by the security of accounts. NFT is relevant to the contest owned by the signer is assured by the part of creating authority seeds of nft_token_vault
+    // contest_owner = signer is assured by has_one on contest. NFT is relevant to the contest owned by the signer is assured by the seeds of nft_vault_account
     // send NFT to the token account owned by contest owner
     let artist_key = ctx.accounts.artwork.artist_key;
     let contest_key = contest.key();
 
-    // transfer tokens to vault account
-    let (_nft_vault_authority, nft_vault_authority_bump) = Pubkey::find_program_address(
-        &[
-            b"nft_vault_authority",
-            contest_key.as_ref(),
-            artist_key.as_ref(),
-        ],
-        ctx.program_id,
-    );
-
+    // transfer NFT to contest owner's token account, signing with the stored bump
     let authority_seeds = &[
-        b"nft_vault_authority".as_ref(),
+        NFT_VAULT_AUTHORITY_SEED,
         contest_key.as_ref(),
         artist_key.as_ref(),
-        &[nft_vault_authority_bump],
+        &[artwork.nft_vault_authority_bump],
     ];
 
-    let claimed_amount = 1 as u64;
+    let claimed_amount: u64 = 1;
     token::transfer(
         ctx.accounts
             .into_transfer_to_pda_context()
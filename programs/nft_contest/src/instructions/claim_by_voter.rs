@@ -1,28 +1,49 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use solana_safe_math::SafeMath;
 
 #[derive(Accounts)]
 pub struct ClaimByVoter<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
     #[account(mut,
-        owner = *program_id)]
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
     pub artwork: Account<'info, Artwork>,
     #[account(mut,
-        owner = *program_id)]
+        owner = *program_id,
+        seeds = [CONTEST_SEED,
+            contest.contest_owner.as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
     pub contest: Account<'info, Contest>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub prize_mint: Account<'info, Mint>,
     #[account(mut,
-        owner = *program_id)]
+        owner = *program_id,
+        seeds = [VOTE_SEED,
+            contest.key().as_ref(),
+            voter.key().as_ref()],
+        bump = vote_data.bump)]
     pub vote_data: Account<'info, VoteData>,
     #[account(mut,
+        seeds = [PRIZE_VAULT_SEED,
+            contest.prize_vault_seeds(&contest.key())[0].as_ref(),
+            contest.prize_vault_seeds(&contest.key())[1].as_ref()],
+        bump = contest.prize_vault_bump,
         token::mint = prize_mint,
         token::authority = prize_vault_authority)]
     pub prize_vault_account: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump = contest.prize_vault_authority_bump)]
     /// CHECK: only used as a signing PDA
     pub prize_vault_authority: UncheckedAccount<'info>,
     #[account(mut,
@@ -42,43 +63,47 @@ pub fn handler(ctx: Context<ClaimByVoter>) -> Result<()> {
     // assert!(vote_end_at < now_ts); // to study: is this safe? safe way to compare?
 
     // check if the vote data belongs to signer
-    assert!(ctx.accounts.vote_data.voter_key == ctx.accounts.voter.key());
+    require_keys_eq!(
+        ctx.accounts.vote_data.voter_key,
+        ctx.accounts.voter.key(),
+        ErrorCode::NotVoter
+    );
 
     // check if artwork account specified matches voted_artwork_id in vote data account specified
-    assert!(ctx.accounts.vote_data.voted_artwork_id == ctx.accounts.artwork.artwork_id);
+    require!(
+        ctx.accounts.vote_data.voted_artwork_id == ctx.accounts.artwork.artwork_id,
+        ErrorCode::VotedArtworkMismatch
+    );
 
-    // fetch most voted artwork
-    let artworks = &contest.artworks_vote_counter;
-    let mut winner_artwork_num_of_votes = 0 as u64;
-    let mut winner_artwork_id = 0;
-    for i in 0..artworks.len() as usize {
-        if artworks[i] > winner_artwork_num_of_votes {
-            winner_artwork_num_of_votes = artworks[i];
-            winner_artwork_id = i as u64;
-        }
-    }
-    // check if the specified artwork account is the best voted artwork
-    assert!(ctx.accounts.artwork.artwork_id == winner_artwork_id);
+    // winners and payouts are recorded by finalize_contest
+    require!(contest.is_finalized, ErrorCode::ContestNotFinalized);
+    // the prize vault may be swept once the claim period is over
+    require!(
+        Clock::get().unwrap().unix_timestamp as u64 <= contest.claim_end_at,
+        ErrorCode::ClaimPeriodEnded
+    );
 
-    // transfer tokens to voter's token account
-    let (_prize_vault_authority, prize_vault_authority_bump) = Pubkey::find_program_address(
-        &[
-            b"prize_vault_authority",
-            ctx.accounts.contest.key().as_ref(),
-        ],
-        ctx.program_id,
+    require!(
+        ctx.accounts.artwork.associated_contest_id == contest.contest_id,
+        ErrorCode::ArtworkContestMismatch
+    );
+    require!(
+        !ctx.accounts.vote_data.reward_claimed,
+        ErrorCode::RewardAlreadyClaimed
     );
+
+    // the amount depends on the voter reward mode, by default only voters of a winner are paid
+    let claimed_amount = contest.voter_reward(&ctx.accounts.vote_data)?;
+    require!(claimed_amount > 0, ErrorCode::NothingToClaim);
+    ctx.accounts.vote_data.reward_claimed = true;
+
+    // transfer tokens to voter's token account, signing with the stored bump
     let contest_key = contest.key();
     let authority_seeds = &[
-        b"prize_vault_authority".as_ref(),
+        PRIZE_VAULT_AUTHORITY_SEED,
         contest_key.as_ref(),
-        &[prize_vault_authority_bump],
+        &[contest.prize_vault_authority_bump],
     ];
-    let prize_amount = contest.prize_amount;
-    let percentage_to_voter = (100 as u8).safe_sub(contest.percentage_to_artist)?;
-    let claimed_amount = (prize_amount * percentage_to_voter as u64)
-        .safe_div(100)?
-        .safe_div(winner_artwork_num_of_votes)?;
     msg!("{}", claimed_amount);
     token::transfer(
         ctx.accounts
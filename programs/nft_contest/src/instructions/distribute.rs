@@ -0,0 +1,116 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(
+        owner = *program_id,
+        has_one = prize_mint,
+        seeds = [CONTEST_SEED,
+            contest.contest_owner.as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
+    pub contest: Account<'info, Contest>,
+    pub prize_mint: Account<'info, Mint>,
+    #[account(mut,
+        seeds = [PRIZE_VAULT_SEED,
+            contest.prize_vault_seeds(&contest.key())[0].as_ref(),
+            contest.prize_vault_seeds(&contest.key())[1].as_ref()],
+        bump = contest.prize_vault_bump,
+        token::mint = prize_mint,
+        token::authority = prize_vault_authority)]
+    pub prize_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump = contest.prize_vault_authority_bump)]
+    /// CHECK: only used as a signing PDA
+    pub prize_vault_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionless crank paying voter rewards in batches, so voters don't have to claim them.
+/// Remaining accounts are pairs of a writable VoteData account and the voter's prize token
+/// account. Votes already paid or not eligible for a reward are skipped.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Distribute<'info>>) -> Result<()> {
+    let contest = &ctx.accounts.contest;
+    require!(contest.is_finalized, ErrorCode::ContestNotFinalized);
+    // the prize vault may be swept once the claim period is over
+    require!(
+        Clock::get().unwrap().unix_timestamp as u64 <= contest.claim_end_at,
+        ErrorCode::ClaimPeriodEnded
+    );
+    let pairs = ctx.remaining_accounts.chunks_exact(2);
+    require!(
+        pairs.remainder().is_empty(),
+        ErrorCode::InvalidRemainingAccounts
+    );
+
+    let contest_key = contest.key();
+    let authority_seeds = &[
+        PRIZE_VAULT_AUTHORITY_SEED,
+        contest_key.as_ref(),
+        &[contest.prize_vault_authority_bump],
+    ];
+
+    for pair in pairs {
+        let mut vote_data: Account<VoteData> = Account::try_from(&pair[0])?;
+        let destination: Account<TokenAccount> = Account::try_from(&pair[1])?;
+
+        // the vote data must be the PDA of this contest and its voter
+        let vote_data_address = Pubkey::create_program_address(
+            &[
+                VOTE_SEED,
+                contest_key.as_ref(),
+                vote_data.voter_key.as_ref(),
+                &[vote_data.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| ErrorCode::VoteDataMismatch)?;
+        require!(
+            vote_data.key() == vote_data_address,
+            ErrorCode::VoteDataMismatch
+        );
+        require!(
+            destination.mint == contest.prize_mint && destination.owner == vote_data.voter_key,
+            ErrorCode::InvalidRewardDestination
+        );
+
+        let claimed_amount = contest.voter_reward(&vote_data)?;
+        if vote_data.reward_claimed || claimed_amount == 0 {
+            continue;
+        }
+        vote_data.reward_claimed = true;
+        vote_data.exit(ctx.program_id)?;
+
+        token::transfer(
+            ctx.accounts
+                .into_transfer_to_voter_context(pair[1].clone())
+                .with_signer(&[&authority_seeds[..]]),
+            claimed_amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+impl<'info> Distribute<'info> {
+    fn into_transfer_to_voter_context(
+        &self,
+        voter_token_account: AccountInfo<'info>,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.prize_vault_account.to_account_info().clone(),
+            to: voter_token_account,
+            authority: self.prize_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -0,0 +1,20 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct FinalizeContest<'info> {
+    #[account(mut,
+        owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+}
+
+/// Permissionless. Freezes the result of tally_votes and the amount every claim pays out.
+pub fn handler(ctx: Context<FinalizeContest>) -> Result<()> {
+    let contest = &mut ctx.accounts.contest;
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(contest.vote_end_at < now_ts, ErrorCode::VotingNotEnded);
+    // finalized contests are refused by finalize with ContestAlreadyFinalized
+    contest.require_status(now_ts, &[ContestStatus::Voting, ContestStatus::Finalized])?;
+    contest.finalize()
+}
@@ -0,0 +1,35 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct IncreaseMaxArtworks<'info> {
+    pub contest_owner: Signer<'info>,
+    #[account(mut,
+        owner = *program_id,
+        has_one = contest_owner)]
+    pub contest: Account<'info, Contest>,
+}
+
+pub fn handler(ctx: Context<IncreaseMaxArtworks>, new_max_artworks: u32) -> Result<()> {
+    // votes live in artwork accounts, so raising the cap does not grow the contest account
+    require!(
+        new_max_artworks > ctx.accounts.contest.max_artworks,
+        ErrorCode::CapacityNotIncreased
+    );
+    // submission may overlap voting, only ended contests take no more artworks
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    let contest = &mut ctx.accounts.contest;
+    contest.require_status(
+        now_ts,
+        &[
+            ContestStatus::Draft,
+            ContestStatus::Open,
+            ContestStatus::Voting,
+        ],
+    )?;
+    contest.advance_status(now_ts);
+    contest.max_artworks = new_max_artworks;
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 
@@ -8,10 +9,10 @@ pub struct Initialize<'info> {
     pub program_owner: Signer<'info>,
     #[account(
         init,
-        seeds = [b"counter".as_ref()],
+        seeds = [COUNTER_SEED],
         bump,
         payer = program_owner,
-        space = 8 + std::mem::size_of::<Counter>()
+        space = Counter::SPACE
     )]
     pub counter: Box<Account<'info, Counter>>,
     pub system_program: Program<'info, System>,
@@ -20,7 +21,9 @@ pub struct Initialize<'info> {
 pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     if ctx.accounts.counter.is_initialized != true {
         ctx.accounts.counter.is_initialized = true;
+        ctx.accounts.counter.version = Counter::VERSION;
         ctx.accounts.counter.contest_count = 0;
+        ctx.accounts.counter.bump = *ctx.bumps.get("counter").unwrap();
         Ok(())
     } else {
         Err(error!(ErrorCode::CounterAlreadyInitialized))
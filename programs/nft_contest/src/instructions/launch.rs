@@ -1,33 +1,54 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, SetAuthority, Token, TokenAccount, Transfer};
+use solana_safe_math::SafeMath;
 use spl_token::instruction::AuthorityType;
 
 #[derive(Accounts)]
-#[instruction(contest_id: u64, vec_size: u8)]
 pub struct Launch<'info> {
     #[account(mut)]
     pub contest_owner: Signer<'info>,
     #[account(mut,
-      seeds = [b"counter"], bump)]
+        seeds = [COUNTER_SEED],
+        bump = counter.bump)]
     pub counter: Account<'info, Counter>,
     #[account(
         init,
-        seeds = [b"contest".as_ref(), 
+        seeds = [CONTEST_SEED,
         contest_owner.key().as_ref(),
-        counter.contest_count.to_string().as_ref()], // contest_count is added for enabling contest_owner to hold multiple contests
+        counter.contest_count.to_le_bytes().as_ref()], // contest_count is added for enabling contest_owner to hold multiple contests
         bump,
         payer = contest_owner,
-        space = 8 + std::mem::size_of::<Contest>() + vec_size as usize
+        space = Contest::SPACE
     )]
     pub contest: Box<Account<'info, Contest>>,
+    #[account(
+        init_if_needed,
+        seeds = [OWNER_PROFILE_SEED,
+        contest_owner.key().as_ref()],
+        bump,
+        payer = contest_owner,
+        space = OwnerProfile::SPACE
+    )]
+    pub owner_profile: Box<Account<'info, OwnerProfile>>,
+    #[account(
+        init_if_needed,
+        seeds = [OWNER_CONTEST_PAGE_SEED,
+        contest_owner.key().as_ref(),
+        (owner_profile.contest_count / CONTESTS_PER_PAGE as u64).to_le_bytes().as_ref()],
+        bump,
+        payer = contest_owner,
+        space = OwnerContestPage::SPACE
+    )]
+    pub owner_contest_page: Box<Account<'info, OwnerContestPage>>,
     /// CHECK: This is not dangerous because we don't read or write from this account
     pub prize_mint: Account<'info, Mint>,
     #[account(
         init,
-        seeds = [b"prize_vault".as_ref(),
-        contest_owner.key().as_ref(),
-        counter.contest_count.to_string().as_ref()], // contest_count is added for enabling contest_owner to hold multiple contests
+        seeds = [PRIZE_VAULT_SEED,
+        contest.key().as_ref()],
         bump,
         payer = contest_owner,
         rent_exempt = enforce,
@@ -44,28 +65,41 @@ pub struct Launch<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[allow(unused)]
-pub fn handler(
-    ctx: Context<Launch>,
-    prize_amount: u64,
-    percentage_to_artist: u8,
-    submit_start_at: u64,
-    submit_end_at: u64,
-    vote_start_at: u64,
-    vote_end_at: u64,
-    title_of_contest: Vec<u8>,
-    link_to_project: Vec<u8>,
-    vec_size: u8,
-) -> Result<()> {
+pub fn handler(ctx: Context<Launch>, args: LaunchArgs) -> Result<()> {
+    let LaunchArgs {
+        prize_amount,
+        percentage_to_artist,
+        submit_start_at,
+        submit_end_at,
+        vote_start_at,
+        vote_end_at,
+        metadata,
+        max_artworks,
+        required_collection,
+        max_entries_per_artist,
+        curation_enabled,
+        judges,
+        judge_weight_percent,
+        artist_vesting_duration,
+        voter_reward_mode,
+        claim_end_at,
+    } = args;
     // check time order
     let now_ts = Clock::get().unwrap().unix_timestamp as u64;
     msg!(&now_ts.to_string());
-    assert!(submit_start_at <= submit_end_at);
-    assert!(vote_start_at <= vote_end_at);
-    assert!(submit_start_at <= vote_start_at);
+    require!(
+        submit_start_at <= submit_end_at
+            && vote_start_at <= vote_end_at
+            && submit_start_at <= vote_start_at
+            // no artwork can be added after votes are tallied
+            && submit_end_at <= vote_end_at,
+        ErrorCode::InvalidSchedule
+    );
 
     // set data in contest account
     ctx.accounts.contest.is_initialized = true;
+    ctx.accounts.contest.version = Contest::VERSION;
+    ctx.accounts.contest.status = ContestStatus::Draft;
 
     // write the current contest_count as contest_id. contest_id for the first contest is 0.
     ctx.accounts.contest.contest_id = ctx.accounts.counter.contest_count;
@@ -82,7 +116,25 @@ pub fn handler(
 
     ctx.accounts.contest.contest_owner = ctx.accounts.contest_owner.key();
 
-    assert!(percentage_to_artist <= 100);
+    // register the contest in the owner's profile, the profile and page are created on first use
+    let owner_profile = &mut ctx.accounts.owner_profile;
+    if owner_profile.contest_count == 0 {
+        owner_profile.owner = ctx.accounts.contest_owner.key();
+        owner_profile.bump = *ctx.bumps.get("owner_profile").unwrap();
+    }
+    let owner_contest_page = &mut ctx.accounts.owner_contest_page;
+    if owner_contest_page.contest_keys.is_empty() {
+        owner_contest_page.owner = ctx.accounts.contest_owner.key();
+        owner_contest_page.page_index = owner_profile.contest_count / CONTESTS_PER_PAGE as u64;
+        owner_contest_page.bump = *ctx.bumps.get("owner_contest_page").unwrap();
+    }
+    owner_contest_page
+        .contest_keys
+        .push(ctx.accounts.contest.key());
+    owner_profile.contest_count += 1;
+
+    require!(percentage_to_artist <= 100, ErrorCode::InvalidPercentage);
+    ctx.accounts.contest.prize_mint = ctx.accounts.prize_mint.key();
     ctx.accounts.contest.prize_amount = prize_amount;
     ctx.accounts.contest.percentage_to_artist = percentage_to_artist;
 
@@ -90,21 +142,58 @@ pub fn handler(
     ctx.accounts.contest.submit_end_at = submit_end_at;
     ctx.accounts.contest.vote_start_at = vote_start_at;
     ctx.accounts.contest.vote_end_at = vote_end_at;
-    ctx.accounts.contest.title_of_contest = title_of_contest;
+    ctx.accounts.contest.advance_status(now_ts);
+    metadata.validate()?;
+    ctx.accounts.contest.set_metadata(metadata);
 
     ctx.accounts.contest.artwork_count = 0;
+    require!(max_artworks > 0, ErrorCode::InvalidMaxArtworks);
+    ctx.accounts.contest.max_artworks = max_artworks;
+    ctx.accounts.contest.required_collection = required_collection;
+    require!(
+        max_entries_per_artist > 0,
+        ErrorCode::InvalidMaxEntriesPerArtist
+    );
+    ctx.accounts.contest.max_entries_per_artist = max_entries_per_artist;
+    require!(
+        !curation_enabled || submit_end_at < vote_start_at,
+        ErrorCode::NoReviewPeriod
+    );
+    ctx.accounts.contest.curation_enabled = curation_enabled;
+    require!(judges.len() <= MAX_JUDGES, ErrorCode::TooManyJudges);
+    require!(
+        judge_weight_percent <= 100 && (judge_weight_percent == 0 || !judges.is_empty()),
+        ErrorCode::InvalidJudgeWeight
+    );
+    ctx.accounts.contest.judges = judges;
+    ctx.accounts.contest.judge_weight_percent = judge_weight_percent;
+    ctx.accounts.contest.artist_vesting_duration = artist_vesting_duration;
+    require!(
+        voter_reward_mode != VoterRewardMode::None || percentage_to_artist == 100,
+        ErrorCode::InvalidVoterRewardMode
+    );
+    ctx.accounts.contest.voter_reward_mode = voter_reward_mode;
+    require!(
+        claim_end_at > vote_end_at.safe_add(artist_vesting_duration)?,
+        ErrorCode::InvalidClaimDeadline
+    );
+    ctx.accounts.contest.claim_end_at = claim_end_at;
+    ctx.accounts.contest.tallied_artwork_count = 0;
 
     // check if cotest owner has enough tokens for prize
-    assert!(ctx.accounts.prize_token_account.amount >= prize_amount);
+    require!(
+        ctx.accounts.prize_token_account.amount >= prize_amount,
+        ErrorCode::InsufficientPrize
+    );
+
+    // store bumps so that later instructions can skip find_program_address
+    ctx.accounts.contest.bump = *ctx.bumps.get("contest").unwrap();
+    ctx.accounts.contest.prize_vault_bump = *ctx.bumps.get("prize_vault_account").unwrap();
 
     // assing the authority of prive vault account to prize vault authority
-    let (prize_vault_authority, _prize_vault_authority_bump) = Pubkey::find_program_address(
-        &[
-            b"prize_vault_authority",
-            ctx.accounts.contest.key().as_ref(),
-        ],
-        ctx.program_id,
-    );
+    let (prize_vault_authority, prize_vault_authority_bump) =
+        find_prize_vault_authority_address(&ctx.accounts.contest.key());
+    ctx.accounts.contest.prize_vault_authority_bump = prize_vault_authority_bump;
     token::set_authority(
         ctx.accounts.into_set_authority_context(),
         AuthorityType::AccountOwner,
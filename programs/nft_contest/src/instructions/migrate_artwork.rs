@@ -0,0 +1,68 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::legacy::{self, ArtworkV0, ContestV0};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+#[derive(Accounts)]
+pub struct MigrateArtwork<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotProgramAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    /// CHECK: decoded from the legacy layout by the handler, it holds the votes of the artwork
+    #[account(
+        owner = *program_id)]
+    pub contest: UncheckedAccount<'info>,
+    /// CHECK: decoded from the legacy layout by the handler
+    #[account(mut,
+        owner = *program_id)]
+    pub artwork: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade authority only. Upgrades a artwork created before accounts were versioned, before its
+/// contest is migrated.
+pub fn handler(ctx: Context<MigrateArtwork>) -> Result<()> {
+    let contest_key = ctx.accounts.contest.key();
+    let artwork_key = ctx.accounts.artwork.key();
+    require!(
+        ctx.accounts.contest.data_len() < Contest::SPACE,
+        ErrorCode::ContestAlreadyMigrated
+    );
+    let contest = legacy::read::<ContestV0, Contest>(&ctx.accounts.contest, Contest::SPACE)?;
+    legacy::migrate(
+        &ctx.accounts.artwork.to_account_info(),
+        Artwork::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        |legacy: ArtworkV0| {
+            require!(
+                legacy.associated_contest_id == contest.contest_id,
+                ErrorCode::ArtworkContestMismatch
+            );
+            let (address, bump) = find_legacy_artwork_address(&contest_key, &legacy.artist_key);
+            require!(address == artwork_key, ErrorCode::InvalidLegacyAccount);
+            let vote_count = contest
+                .artworks_vote_counter
+                .get(legacy.artwork_id as usize)
+                .copied()
+                .unwrap_or(0);
+
+            let mut artwork = Artwork::from(legacy);
+            artwork.vote_count = vote_count;
+            artwork.vote_weight_total = vote_count;
+            artwork.bump = bump;
+            artwork.nft_vault_bump =
+                find_legacy_nft_vault_address(&contest_key, &artwork.artist_key).1;
+            artwork.nft_vault_authority_bump =
+                find_nft_vault_authority_address(&contest_key, &artwork.artist_key).1;
+            Ok(artwork)
+        },
+    )
+}
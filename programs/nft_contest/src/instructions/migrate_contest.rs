@@ -0,0 +1,111 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::legacy::{self, ContestV0};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct MigrateContest<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotProgramAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    /// CHECK: decoded from the legacy layout by the handler
+    #[account(mut,
+        owner = *program_id)]
+    pub contest: UncheckedAccount<'info>,
+    // the legacy layout has no prize mint, it is read from the vault
+    #[account(
+        token::authority = prize_vault_authority)]
+    pub prize_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump)]
+    /// CHECK: only used as a PDA
+    pub prize_vault_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade authority only. Upgrades a contest created before accounts were versioned. Its artworks
+/// must be migrated first, as they read their votes from the legacy contest, and are passed as
+/// remaining accounts so that none of those votes is dropped.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, MigrateContest<'info>>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    let contest_key = ctx.accounts.contest.key();
+    let prize_vault = &ctx.accounts.prize_vault_account;
+    let prize_vault_authority_bump = *ctx.bumps.get("prize_vault_authority").unwrap();
+    legacy::migrate(
+        &ctx.accounts.contest.to_account_info(),
+        Contest::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        |legacy: ContestV0| {
+            let (address, bump) =
+                find_legacy_contest_address(&legacy.contest_owner, legacy.contest_id);
+            require!(address == contest_key, ErrorCode::InvalidLegacyAccount);
+            let (prize_vault_address, prize_vault_bump) =
+                find_legacy_prize_vault_address(&legacy.contest_owner, legacy.contest_id);
+            require!(
+                prize_vault_address == prize_vault.key(),
+                ErrorCode::InvalidLegacyAccount
+            );
+
+            require_artworks_migrated(&contest_key, &legacy, ctx.remaining_accounts)?;
+
+            let mut contest = Contest::from(legacy);
+            contest.prize_mint = prize_vault.mint;
+            contest.bump = bump;
+            contest.prize_vault_bump = prize_vault_bump;
+            contest.prize_vault_authority_bump = prize_vault_authority_bump;
+            contest.advance_status(now_ts);
+            Ok(contest)
+        },
+    )
+}
+
+/// Checks that `artworks` are the artworks of the legacy contest at `contest_key`, every one of
+/// them in the current layout.
+fn require_artworks_migrated(
+    contest_key: &Pubkey,
+    legacy: &ContestV0,
+    artworks: &[AccountInfo],
+) -> Result<()> {
+    let mut migrated = vec![false; legacy.artwork_count as usize];
+    for artwork_info in artworks {
+        require!(
+            artwork_info.data_len() >= Artwork::SPACE,
+            ErrorCode::ArtworksNotMigrated
+        );
+        let artwork: Account<Artwork> = Account::try_from(artwork_info)?;
+        require!(
+            artwork.associated_contest_id == legacy.contest_id,
+            ErrorCode::ArtworkContestMismatch
+        );
+        let address = Pubkey::create_program_address(
+            &[
+                ARTWORK_SEED,
+                contest_key.as_ref(),
+                artwork.artist_key.as_ref(),
+                &[artwork.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidLegacyAccount)?;
+        require!(address == artwork.key(), ErrorCode::InvalidLegacyAccount);
+        if let Some(flag) = migrated.get_mut(artwork.artwork_id as usize) {
+            *flag = true;
+        }
+    }
+    require!(
+        migrated.iter().all(|flag| *flag),
+        ErrorCode::ArtworksNotMigrated
+    );
+    Ok(())
+}
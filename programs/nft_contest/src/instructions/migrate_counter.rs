@@ -0,0 +1,40 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::legacy::{self, CounterV0};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+#[derive(Accounts)]
+pub struct MigrateCounter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotProgramAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    /// CHECK: decoded from the legacy layout by the handler
+    #[account(mut,
+        seeds = [COUNTER_SEED],
+        bump)]
+    pub counter: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade authority only. Upgrades a counter created before accounts were versioned.
+pub fn handler(ctx: Context<MigrateCounter>) -> Result<()> {
+    let bump = *ctx.bumps.get("counter").unwrap();
+    legacy::migrate(
+        &ctx.accounts.counter.to_account_info(),
+        Counter::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        |legacy: CounterV0| {
+            let mut counter = Counter::from(legacy);
+            counter.bump = bump;
+            Ok(counter)
+        },
+    )
+}
@@ -0,0 +1,48 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::legacy::{self, VoteDataV0};
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+#[derive(Accounts)]
+pub struct MigrateVoteData<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::NotProgramAdmin)]
+    pub program_data: Account<'info, ProgramData>,
+    /// CHECK: only its address is used, the legacy vote data does not record it
+    #[account(
+        owner = *program_id)]
+    pub contest: UncheckedAccount<'info>,
+    /// CHECK: decoded from the legacy layout by the handler
+    #[account(mut,
+        owner = *program_id)]
+    pub vote_data: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade authority only. Upgrades a vote data created before accounts were versioned.
+pub fn handler(ctx: Context<MigrateVoteData>) -> Result<()> {
+    let contest_key = ctx.accounts.contest.key();
+    let vote_data_key = ctx.accounts.vote_data.key();
+    legacy::migrate(
+        &ctx.accounts.vote_data.to_account_info(),
+        VoteData::SPACE,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        |legacy: VoteDataV0| {
+            let (address, bump) = find_vote_data_address(&contest_key, &legacy.voter_key);
+            require!(address == vote_data_key, ErrorCode::InvalidLegacyAccount);
+
+            let mut vote_data = VoteData::from(legacy);
+            vote_data.contest = contest_key;
+            vote_data.bump = bump;
+            Ok(vote_data)
+        },
+    )
+}
@@ -1,15 +1,49 @@
+pub mod approve_artwork;
+pub mod cancel_contest;
 pub mod claim_by_artist;
 pub mod claim_by_contest_owner;
 pub mod claim_by_voter;
+pub mod distribute;
+pub mod finalize_contest;
+pub mod increase_max_artworks;
 pub mod initialize;
 pub mod launch;
+pub mod migrate_artwork;
+pub mod migrate_contest;
+pub mod migrate_counter;
+pub mod migrate_vote_data;
+pub mod reject_artwork;
+pub mod score_artwork;
+pub mod set_curators;
 pub mod submit;
+pub mod sweep_remainder;
+pub mod tally_votes;
+pub mod unlock_vote;
+pub mod update_contest_metadata;
 pub mod vote;
+pub mod withdraw_artwork;
 
+pub use approve_artwork::*;
+pub use cancel_contest::*;
 pub use claim_by_artist::*;
 pub use claim_by_contest_owner::*;
 pub use claim_by_voter::*;
+pub use distribute::*;
+pub use finalize_contest::*;
+pub use increase_max_artworks::*;
 pub use initialize::*;
 pub use launch::*;
+pub use migrate_artwork::*;
+pub use migrate_contest::*;
+pub use migrate_counter::*;
+pub use migrate_vote_data::*;
+pub use reject_artwork::*;
+pub use score_artwork::*;
+pub use set_curators::*;
 pub use submit::*;
+pub use sweep_remainder::*;
+pub use tally_votes::*;
+pub use unlock_vote::*;
+pub use update_contest_metadata::*;
 pub use vote::*;
+pub use withdraw_artwork::*;
@@ -0,0 +1,84 @@
+use crate::instructions::approve_artwork::check_review;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct RejectArtwork<'info> {
+    pub reviewer: Signer<'info>,
+    #[account(owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+    #[account(mut,
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
+    pub artwork: Account<'info, Artwork>,
+    pub nft_mint: Account<'info, Mint>,
+    #[account(mut,
+        seeds = [NFT_VAULT_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.nft_vault_bump,
+        token::mint = nft_mint,
+        token::authority = nft_vault_authority)]
+    pub nft_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [NFT_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref()],
+        bump = artwork.nft_vault_authority_bump)]
+    /// CHECK: only used as a signing PDA
+    pub nft_vault_authority: UncheckedAccount<'info>,
+    #[account(mut,
+        token::mint = nft_mint,
+        token::authority = artwork.artist_key)]
+    pub artist_nft_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<RejectArtwork>) -> Result<()> {
+    check_review(
+        &ctx.accounts.contest,
+        &ctx.accounts.artwork,
+        &ctx.accounts.reviewer.key(),
+    )?;
+
+    ctx.accounts.artwork.status = ArtworkStatus::Rejected;
+
+    // return the NFT to the artist
+    let contest_key = ctx.accounts.contest.key();
+    let artist_key = ctx.accounts.artwork.artist_key;
+    let authority_seeds = &[
+        NFT_VAULT_AUTHORITY_SEED,
+        contest_key.as_ref(),
+        artist_key.as_ref(),
+        &[ctx.accounts.artwork.nft_vault_authority_bump],
+    ];
+    token::transfer(
+        ctx.accounts
+            .into_transfer_to_artist_context()
+            .with_signer(&[&authority_seeds[..]]),
+        1,
+    )?;
+
+    Ok(())
+}
+
+impl<'info> RejectArtwork<'info> {
+    fn into_transfer_to_artist_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.nft_vault_account.to_account_info().clone(),
+            to: self.artist_nft_token_account.to_account_info().clone(),
+            authority: self.nft_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use solana_safe_math::SafeMath;
+
+#[derive(Accounts)]
+pub struct ScoreArtwork<'info> {
+    #[account(mut)]
+    pub judge: Signer<'info>,
+    #[account(owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+    #[account(mut,
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
+    pub artwork: Account<'info, Artwork>,
+    #[account(
+        init,
+        seeds = [JUDGE_SCORE_SEED, // a judge can score an artwork only once
+            artwork.key().as_ref(),
+            judge.key().as_ref()],
+        bump,
+        payer = judge,
+        space = JudgeScore::SPACE,
+    )]
+    pub judge_score: Account<'info, JudgeScore>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ScoreArtwork>, score: u8) -> Result<()> {
+    let contest = &ctx.accounts.contest;
+    require!(
+        contest.judges.contains(&ctx.accounts.judge.key()),
+        ErrorCode::NotJudge
+    );
+
+    // judges score the final set of artworks, until voting ends
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        contest.submit_end_at < now_ts && now_ts <= contest.vote_end_at,
+        ErrorCode::NotInJudgingPeriod
+    );
+    contest.require_status(now_ts, &[ContestStatus::Open, ContestStatus::Voting])?;
+    require!(score <= MAX_JUDGE_SCORE, ErrorCode::InvalidScore);
+    require!(
+        ctx.accounts.artwork.status == ArtworkStatus::Approved,
+        ErrorCode::ArtworkNotApproved
+    );
+
+    // set data in JudgeScore account
+    ctx.accounts.judge_score.artwork = ctx.accounts.artwork.key();
+    ctx.accounts.judge_score.judge = ctx.accounts.judge.key();
+    ctx.accounts.judge_score.score = score;
+    ctx.accounts.judge_score.bump = *ctx.bumps.get("judge_score").unwrap();
+
+    ctx.accounts.artwork.judge_score_total = ctx
+        .accounts
+        .artwork
+        .judge_score_total
+        .safe_add(score as u64)?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCurators<'info> {
+    pub contest_owner: Signer<'info>,
+    #[account(mut,
+        owner = *program_id,
+        has_one = contest_owner)]
+    pub contest: Account<'info, Contest>,
+}
+
+/// Replaces the curators who can review artworks on behalf of the contest owner.
+pub fn handler(ctx: Context<SetCurators>, curators: Vec<Pubkey>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        now_ts < ctx.accounts.contest.vote_start_at,
+        ErrorCode::CuratorsLocked
+    );
+    let contest = &mut ctx.accounts.contest;
+    contest.require_status(now_ts, &[ContestStatus::Draft, ContestStatus::Open])?;
+    contest.advance_status(now_ts);
+    require!(curators.len() <= MAX_CURATORS, ErrorCode::TooManyCurators);
+
+    contest.curators = curators;
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, SetAuthority, Token, TokenAccount, Transfer};
+use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
 use spl_token::instruction::AuthorityType;
 
 #[derive(Accounts)]
@@ -10,25 +13,47 @@ pub struct Submit<'info> {
     #[account(mut,
         owner = *program_id)]
     pub contest: Account<'info, Contest>,
+    #[account(
+        init_if_needed,
+        seeds = [ARTIST_ENTRIES_SEED,
+            contest.key().as_ref(),
+            artist.key().as_ref()],
+        bump,
+        payer = artist,
+        space = ArtistEntries::SPACE,
+    )]
+    pub artist_entries: Box<Account<'info, ArtistEntries>>,
     #[account(
         init,
-        seeds = [b"artwork".as_ref(),
+        seeds = [ARTWORK_SEED,
             contest.key().as_ref(),
             artist.key().as_ref(),
-            ], // an artist i.e. wallet can submit only one artwork per contest
+            artist_entries.entry_count.to_le_bytes().as_ref(),
+            ], // an artist i.e. wallet can submit up to max_entries_per_artist artworks per contest
         bump,
         payer = artist,
-        space = 8 + std::mem::size_of::<Artwork>(),
+        space = Artwork::SPACE,
     )]
     pub artwork: Box<Account<'info, Artwork>>,
-    /// CHECK: This is not dangerous because we don't read or write from this account
-    #[account(mint::decimals = 0)]
+    // a genuine NFT has a supply of exactly one and nobody can mint more
+    #[account(mint::decimals = 0,
+        constraint = nft_mint.supply == 1 @ ErrorCode::NotAnNft,
+        constraint = nft_mint.mint_authority.is_none() @ ErrorCode::NotAnNft)]
     pub nft_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [mpl_token_metadata::state::PREFIX.as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            nft_mint.key().as_ref()],
+        bump,
+        seeds::program = mpl_token_metadata::ID)]
+    /// CHECK: deserialized and checked against the mint and the artist in the handler
+    pub nft_metadata: UncheckedAccount<'info>,
     #[account(
         init,
-        seeds = [b"nft_vault".as_ref(), 
+        seeds = [NFT_VAULT_SEED,
             contest.key().as_ref(),
-            artist.key().as_ref()],
+            artist.key().as_ref(),
+            artist_entries.entry_count.to_le_bytes().as_ref()],
         bump,
         payer = artist,
         token::mint = nft_mint,
@@ -48,13 +73,51 @@ pub struct Submit<'info> {
 pub fn handler(ctx: Context<Submit>) -> Result<()> {
     // check time order
     let contest = &mut ctx.accounts.contest;
-    // let submit_start_at = contest.submit_start_at;
-    let submit_end_at = contest.submit_end_at;
     let now_ts = Clock::get().unwrap().unix_timestamp as u64;
-    assert!(now_ts <= submit_end_at);
+    require!(now_ts <= contest.submit_end_at, ErrorCode::SubmissionClosed);
+    // submission may overlap voting
+    contest.require_status(now_ts, &[ContestStatus::Open, ContestStatus::Voting])?;
+    contest.advance_status(now_ts);
+
+    // check if the contest still has room for another artwork
+    require!(
+        contest.artwork_count < contest.max_artworks as u64,
+        ErrorCode::ContestFull
+    );
+
+    // check if the artist can still enter another artwork, the entries account is created on first use
+    let artist_entries = &mut ctx.accounts.artist_entries;
+    if artist_entries.entry_count == 0 {
+        artist_entries.contest = contest.key();
+        artist_entries.artist = ctx.accounts.artist.key();
+        artist_entries.bump = *ctx.bumps.get("artist_entries").unwrap();
+    }
+    require!(
+        artist_entries.entry_count < contest.max_entries_per_artist,
+        ErrorCode::TooManyEntries
+    );
+    ctx.accounts.artwork.entry_index = artist_entries.entry_count;
+    artist_entries.entry_count += 1;
+
+    // check if the NFT has Metaplex metadata created by the artist
+    let metadata = verify_nft_metadata(
+        &ctx.accounts.nft_metadata,
+        &ctx.accounts.nft_mint.key(),
+        &ctx.accounts.artist.key(),
+    )?;
+
+    // check if the NFT belongs to the collection required by the contest, if any
+    if let Some(required_collection) = contest.required_collection {
+        let is_in_collection = matches!(
+            metadata.collection,
+            Some(collection) if collection.verified && collection.key == required_collection
+        );
+        require!(is_in_collection, ErrorCode::CollectionMismatch);
+    }
 
     // set data in artwork account
     ctx.accounts.artwork.is_initialized = true;
+    ctx.accounts.artwork.version = Artwork::VERSION;
 
     // set artwork id in Artwork account = artwork count in Contest account. Id for The first artwork for any contest is 0.
     ctx.accounts.artwork.artwork_id = contest.artwork_count;
@@ -66,17 +129,24 @@ pub fn handler(ctx: Context<Submit>) -> Result<()> {
     // write pubkey of NFT token account in artwork account
     ctx.accounts.artwork.artwork_token_account = ctx.accounts.artwork_token_account.key();
 
-    // put 0 as num of positive votes in contest account
-    contest.artworks_vote_counter.push(0);
+    // votes are counted on the artwork account itself
+    ctx.accounts.artwork.vote_count = 0;
+    ctx.accounts.artwork.vote_weight_total = 0;
+    ctx.accounts.artwork.judge_score_total = 0;
+    ctx.accounts.artwork.prize_released_amount = 0;
+    // without curation every artwork is votable right away
+    ctx.accounts.artwork.status = if contest.curation_enabled {
+        ArtworkStatus::Pending
+    } else {
+        ArtworkStatus::Approved
+    };
 
-    let (nft_vault_authority, _nft_vault_authority_bump) = Pubkey::find_program_address(
-        &[
-            b"nft_vault_authority",
-            ctx.accounts.contest.key().as_ref(),
-            ctx.accounts.artist.key().as_ref(),
-        ],
-        ctx.program_id,
-    );
+    // store bumps so that later instructions can skip find_program_address
+    ctx.accounts.artwork.bump = *ctx.bumps.get("artwork").unwrap();
+    ctx.accounts.artwork.nft_vault_bump = *ctx.bumps.get("nft_vault_account").unwrap();
+    let (nft_vault_authority, nft_vault_authority_bump) =
+        find_nft_vault_authority_address(&ctx.accounts.contest.key(), &ctx.accounts.artist.key());
+    ctx.accounts.artwork.nft_vault_authority_bump = nft_vault_authority_bump;
     token::set_authority(
         ctx.accounts.into_set_authority_context(),
         AuthorityType::AccountOwner,
@@ -89,6 +159,27 @@ pub fn handler(ctx: Context<Submit>) -> Result<()> {
     Ok(())
 }
 
+fn verify_nft_metadata(
+    nft_metadata: &AccountInfo,
+    nft_mint: &Pubkey,
+    artist: &Pubkey,
+) -> Result<Metadata> {
+    // from_account_info checks that the account is owned by the token metadata program
+    let metadata = Metadata::from_account_info(nft_metadata)
+        .map_err(|_| error!(ErrorCode::InvalidNftMetadata))?;
+    require!(metadata.mint == *nft_mint, ErrorCode::InvalidNftMetadata);
+
+    let is_verified_creator = metadata
+        .data
+        .creators
+        .iter()
+        .flatten()
+        .any(|creator| creator.address == *artist && creator.verified);
+    require!(is_verified_creator, ErrorCode::ArtistNotVerifiedCreator);
+
+    Ok(metadata)
+}
+
 impl<'info> Submit<'info> {
     fn into_transfer_to_pda_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
         let cpi_accounts = Transfer {
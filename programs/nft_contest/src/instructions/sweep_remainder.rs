@@ -0,0 +1,115 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct SweepRemainder<'info> {
+    #[account(mut)]
+    pub contest_owner: Signer<'info>,
+    #[account(mut,
+        owner = *program_id,
+        has_one = contest_owner,
+        has_one = prize_mint,
+        seeds = [CONTEST_SEED,
+            contest_owner.key().as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
+    pub contest: Account<'info, Contest>,
+    pub prize_mint: Account<'info, Mint>,
+    #[account(mut,
+        seeds = [PRIZE_VAULT_SEED,
+            contest.prize_vault_seeds(&contest.key())[0].as_ref(),
+            contest.prize_vault_seeds(&contest.key())[1].as_ref()],
+        bump = contest.prize_vault_bump,
+        token::mint = prize_mint,
+        token::authority = prize_vault_authority)]
+    pub prize_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [PRIZE_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref()],
+        bump = contest.prize_vault_authority_bump)]
+    /// CHECK: only used as a signing PDA
+    pub prize_vault_authority: UncheckedAccount<'info>,
+    // any account of the prize mint chosen by the owner, e.g. a treasury
+    #[account(mut,
+        token::mint = prize_mint)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sends what is left in the prize vault after the claim deadline, rounding dust and unclaimed
+/// shares, to the destination token account, then closes the vault and refunds its rent.
+pub fn handler(ctx: Context<SweepRemainder>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.contest.claim_end_at < now_ts,
+        ErrorCode::ClaimPeriodNotEnded
+    );
+    // unfinalized contests still owe the winners a prize, cancelled ones have no vault left
+    ctx.accounts
+        .contest
+        .require_status(now_ts, &[ContestStatus::Finalized])?;
+    ctx.accounts.contest.status = ContestStatus::Settled;
+
+    let contest = &ctx.accounts.contest;
+
+    let contest_key = contest.key();
+    let authority_seeds = &[
+        PRIZE_VAULT_AUTHORITY_SEED,
+        contest_key.as_ref(),
+        &[contest.prize_vault_authority_bump],
+    ];
+
+    // a token account can only be closed once it is empty
+    let remainder = ctx.accounts.prize_vault_account.amount;
+    msg!(
+        "swept {} to {}",
+        remainder,
+        ctx.accounts.destination_token_account.key()
+    );
+    if remainder > 0 {
+        token::transfer(
+            ctx.accounts
+                .into_transfer_to_destination_context()
+                .with_signer(&[&authority_seeds[..]]),
+            remainder,
+        )?;
+    }
+    token::close_account(
+        ctx.accounts
+            .into_close_vault_context()
+            .with_signer(&[&authority_seeds[..]]),
+    )?;
+
+    Ok(())
+}
+
+impl<'info> SweepRemainder<'info> {
+    fn into_transfer_to_destination_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.prize_vault_account.to_account_info().clone(),
+            to: self.destination_token_account.to_account_info().clone(),
+            authority: self.prize_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+
+    fn into_close_vault_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.prize_vault_account.to_account_info().clone(),
+            destination: self.contest_owner.to_account_info().clone(),
+            authority: self.prize_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
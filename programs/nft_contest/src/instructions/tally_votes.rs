@@ -0,0 +1,41 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TallyVotes<'info> {
+    #[account(mut,
+        owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+}
+
+/// Permissionless crank run after voting ends. Artwork accounts of the contest are passed as
+/// remaining accounts in order of artwork id, continuing from `tallied_artwork_count`, so the
+/// tally of a large contest can be spread over as many transactions as needed.
+/// When judge scores are mixed with votes, every artwork is passed twice: once to sum the
+/// votes, then once more to compute scores.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, TallyVotes<'info>>) -> Result<()> {
+    let contest = &mut ctx.accounts.contest;
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(contest.vote_end_at < now_ts, ErrorCode::VotingNotEnded);
+    require!(!contest.is_finalized, ErrorCode::ContestAlreadyFinalized);
+    contest.require_status(now_ts, &[ContestStatus::Voting])?;
+    contest.advance_status(now_ts);
+
+    for artwork_info in ctx.remaining_accounts.iter() {
+        let artwork: Account<Artwork> = Account::try_from(artwork_info)?;
+        require!(
+            artwork.associated_contest_id == contest.contest_id,
+            ErrorCode::ArtworkContestMismatch
+        );
+        require!(
+            artwork.artwork_id == contest.tallied_artwork_count,
+            ErrorCode::ArtworkOutOfOrder
+        );
+
+        contest.tally_artwork(&artwork)?;
+    }
+    contest.advance_tally_pass();
+
+    Ok(())
+}
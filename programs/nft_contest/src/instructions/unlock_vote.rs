@@ -0,0 +1,86 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct UnlockVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    #[account(owner = *program_id)]
+    pub contest: Account<'info, Contest>,
+    #[account(mut,
+        seeds = [VOTE_ESCROW_SEED,
+            contest.key().as_ref(),
+            voter.key().as_ref()],
+        bump,
+        token::authority = vote_escrow)]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    #[account(mut,
+        token::mint = vote_escrow.mint,
+        token::authority = voter)]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns the prize tokens locked by a weighted vote once voting has ended, and closes the escrow
+/// refunding its rent to the voter.
+pub fn handler(ctx: Context<UnlockVote>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.contest.vote_end_at < now_ts,
+        ErrorCode::VotingNotEnded
+    );
+
+    let contest_key = ctx.accounts.contest.key();
+    let voter_key = ctx.accounts.voter.key();
+    let escrow_seeds = &[
+        VOTE_ESCROW_SEED,
+        contest_key.as_ref(),
+        voter_key.as_ref(),
+        &[*ctx.bumps.get("vote_escrow").unwrap()],
+    ];
+    let locked = ctx.accounts.vote_escrow.amount;
+    if locked > 0 {
+        token::transfer(
+            ctx.accounts
+                .into_transfer_to_voter_context()
+                .with_signer(&[&escrow_seeds[..]]),
+            locked,
+        )?;
+    }
+    token::close_account(
+        ctx.accounts
+            .into_close_escrow_context()
+            .with_signer(&[&escrow_seeds[..]]),
+    )?;
+
+    Ok(())
+}
+
+impl<'info> UnlockVote<'info> {
+    fn into_transfer_to_voter_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.vote_escrow.to_account_info().clone(),
+            to: self.voter_token_account.to_account_info().clone(),
+            authority: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+
+    fn into_close_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.vote_escrow.to_account_info().clone(),
+            destination: self.voter.to_account_info().clone(),
+            authority: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -0,0 +1,29 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateContestMetadata<'info> {
+    pub contest_owner: Signer<'info>,
+    #[account(mut,
+        owner = *program_id,
+        has_one = contest_owner)]
+    pub contest: Account<'info, Contest>,
+}
+
+pub fn handler(ctx: Context<UpdateContestMetadata>, metadata: ContestMetadata) -> Result<()> {
+    // metadata is frozen once voters can see the contest
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        now_ts < ctx.accounts.contest.vote_start_at,
+        ErrorCode::MetadataLocked
+    );
+    let contest = &mut ctx.accounts.contest;
+    contest.require_status(now_ts, &[ContestStatus::Draft, ContestStatus::Open])?;
+    contest.advance_status(now_ts);
+
+    metadata.validate()?;
+    contest.set_metadata(metadata);
+
+    Ok(())
+}
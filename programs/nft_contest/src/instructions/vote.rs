@@ -1,51 +1,208 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
 use crate::state::*;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    self, Allocate, Assign, CreateAccount, Transfer as SystemTransfer,
+};
+use anchor_spl::token::{self, InitializeAccount, Mint, Token, TokenAccount, Transfer};
+use solana_safe_math::SafeMath;
 
 #[derive(Accounts)]
 pub struct Vote<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
-    #[account(mut)]
+    #[account(mut,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artwork.artist_key.as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
     pub artwork: Account<'info, Artwork>,
-    #[account(mut)]
+    // read only, so that votes for different artworks can be processed in parallel
     pub contest: Account<'info, Contest>,
     #[account(
         init,
-        seeds = [b"vote".as_ref(), // One can vote only one time per contest with the current structure.
-            contest.key().to_bytes().as_ref(),
-            voter.key.to_bytes().as_ref()],
+        seeds = [VOTE_SEED, // One can vote only one time per contest with the current structure.
+            contest.key().as_ref(),
+            voter.key().as_ref()],
         bump,
         payer = voter,
-        space = 8 + std::mem::size_of::<VoteData>(),
+        space = VoteData::SPACE,
     )]
     pub vote_data: Account<'info, VoteData>,
+    #[account(address = contest.prize_mint @ ErrorCode::PrizeMintMismatch)]
+    pub prize_mint: Account<'info, Mint>,
+    // its balance is the weight of the vote in contests rewarding voters by weight
+    #[account(mut,
+        token::authority = voter,
+        constraint = voter_token_account.mint == contest.prize_mint @ ErrorCode::PrizeMintMismatch)]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    #[account(mut,
+        seeds = [VOTE_ESCROW_SEED,
+            contest.key().as_ref(),
+            voter.key().as_ref()],
+        bump)]
+    /// CHECK: created by the handler, only in contests rewarding voters by weight
+    pub vote_escrow: UncheckedAccount<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Vote>, voted_artwork_id: usize) -> Result<()> {
+pub fn handler(ctx: Context<Vote>, voted_artwork_id: u64) -> Result<()> {
     // check time order
-    let contest = &mut ctx.accounts.contest;
-    // let vote_start_at = contest.vote_start_at;
-    // let vote_end_at = contest.vote_end_at;
-    // let now_ts = Clock::get().unwrap().unix_timestamp as u64;
-    // assert!(vote_start_at <= now_ts);
-    // assert!(now_ts <= vote_end_at);
+    let contest = &ctx.accounts.contest;
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        contest.vote_start_at <= now_ts && now_ts <= contest.vote_end_at,
+        ErrorCode::NotInVotingPeriod
+    );
+    contest.require_status(now_ts, &[ContestStatus::Voting])?;
 
     // check if voted_artwork_id input matches with specified artwork account
-    assert!(
-        voted_artwork_id as u64 == ctx.accounts.artwork.artwork_id,
-        "voted_artwork_id is not correct"
+    require!(
+        voted_artwork_id == ctx.accounts.artwork.artwork_id,
+        ErrorCode::VotedArtworkMismatch
     );
     // check if specified artwork account and specified contest account match
-    assert!(ctx.accounts.artwork.associated_contest_id == contest.contest_id);
+    require!(
+        ctx.accounts.artwork.associated_contest_id == contest.contest_id,
+        ErrorCode::ArtworkContestMismatch
+    );
+
+    // check if the artwork passed curation
+    require!(
+        ctx.accounts.artwork.status == ArtworkStatus::Approved,
+        ErrorCode::ArtworkNotApproved
+    );
 
     // set data in VoteData account
     ctx.accounts.vote_data.is_initialized = true;
+    ctx.accounts.vote_data.version = VoteData::VERSION;
+    ctx.accounts.vote_data.contest = ctx.accounts.contest.key();
     ctx.accounts.vote_data.voter_key = ctx.accounts.voter.key();
-    ctx.accounts.vote_data.voted_artwork_id = voted_artwork_id as u64;
+    ctx.accounts.vote_data.voted_artwork_id = voted_artwork_id;
+    ctx.accounts.vote_data.weight = match contest.voter_reward_mode {
+        VoterRewardMode::VoteWeighted => {
+            // the balance is locked until voting ends, so that it can not be moved and counted again
+            let weight = ctx.accounts.voter_token_account.amount;
+            let contest_key = ctx.accounts.contest.key();
+            let voter_key = ctx.accounts.voter.key();
+            let escrow_seeds = &[
+                VOTE_ESCROW_SEED,
+                contest_key.as_ref(),
+                voter_key.as_ref(),
+                &[*ctx.bumps.get("vote_escrow").unwrap()],
+            ];
+            ctx.accounts.create_escrow(&escrow_seeds[..])?;
+            token::initialize_account(ctx.accounts.into_initialize_escrow_context())?;
+            token::transfer(ctx.accounts.into_transfer_to_escrow_context(), weight)?;
+            weight
+        }
+        _ => 1,
+    };
+    ctx.accounts.vote_data.reward_claimed = false;
+    ctx.accounts.vote_data.bump = *ctx.bumps.get("vote_data").unwrap();
 
-    // add 1 to the corresponding place of order in artworks vote counter
-    contest.artworks_vote_counter[voted_artwork_id] += 1;
+    // add 1 to the vote count of the voted artwork
+    ctx.accounts.artwork.vote_count = ctx.accounts.artwork.vote_count.safe_add(1)?;
+    ctx.accounts.artwork.vote_weight_total = ctx
+        .accounts
+        .artwork
+        .vote_weight_total
+        .safe_add(ctx.accounts.vote_data.weight)?;
 
     Ok(())
 }
+
+impl<'info> Vote<'info> {
+    /// Creates the escrow token account at its PDA. Anyone can send lamports to that address
+    /// beforehand, which create_account refuses, so a funded address is topped up, allocated and
+    /// assigned instead, like Anchor's `init` does.
+    fn create_escrow(&self, escrow_seeds: &[&[u8]]) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(TokenAccount::LEN);
+        let lamports = self.vote_escrow.lamports();
+        if lamports == 0 {
+            return system_program::create_account(
+                self.into_create_escrow_context()
+                    .with_signer(&[escrow_seeds]),
+                rent,
+                TokenAccount::LEN as u64,
+                &token::ID,
+            );
+        }
+        let shortfall = rent.saturating_sub(lamports);
+        if shortfall > 0 {
+            system_program::transfer(self.into_fund_escrow_context(), shortfall)?;
+        }
+        system_program::allocate(
+            self.into_allocate_escrow_context()
+                .with_signer(&[escrow_seeds]),
+            TokenAccount::LEN as u64,
+        )?;
+        system_program::assign(
+            self.into_assign_escrow_context()
+                .with_signer(&[escrow_seeds]),
+            &token::ID,
+        )
+    }
+
+    fn into_create_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, CreateAccount<'info>> {
+        let cpi_accounts = CreateAccount {
+            from: self.voter.to_account_info().clone(),
+            to: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    fn into_fund_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, SystemTransfer<'info>> {
+        let cpi_accounts = SystemTransfer {
+            from: self.voter.to_account_info().clone(),
+            to: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    fn into_allocate_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, Allocate<'info>> {
+        let cpi_accounts = Allocate {
+            account_to_allocate: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    fn into_assign_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, Assign<'info>> {
+        let cpi_accounts = Assign {
+            account_to_assign: self.vote_escrow.to_account_info().clone(),
+        };
+        CpiContext::new(self.system_program.to_account_info().clone(), cpi_accounts)
+    }
+
+    // the escrow is its own authority
+    fn into_initialize_escrow_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, InitializeAccount<'info>> {
+        let cpi_accounts = InitializeAccount {
+            account: self.vote_escrow.to_account_info().clone(),
+            mint: self.prize_mint.to_account_info().clone(),
+            authority: self.vote_escrow.to_account_info().clone(),
+            rent: self.rent.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+
+    fn into_transfer_to_escrow_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.voter_token_account.to_account_info().clone(),
+            to: self.vote_escrow.to_account_info().clone(),
+            authority: self.voter.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use crate::common::lib::ErrorCode;
+use crate::pda::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawArtwork<'info> {
+    pub artist: Signer<'info>,
+    #[account(
+        owner = *program_id,
+        seeds = [CONTEST_SEED,
+            contest.contest_owner.as_ref(),
+            contest.id_seed().as_ref()],
+        bump = contest.bump)]
+    pub contest: Account<'info, Contest>,
+    #[account(mut,
+        owner = *program_id,
+        seeds = [ARTWORK_SEED,
+            contest.key().as_ref(),
+            artist.key().as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.bump)]
+    pub artwork: Account<'info, Artwork>,
+    pub nft_mint: Account<'info, Mint>,
+    #[account(mut,
+        seeds = [NFT_VAULT_SEED,
+            contest.key().as_ref(),
+            artist.key().as_ref(),
+            artwork.entry_seed().as_ref()],
+        bump = artwork.nft_vault_bump,
+        token::mint = nft_mint,
+        token::authority = nft_vault_authority)]
+    pub nft_vault_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [NFT_VAULT_AUTHORITY_SEED,
+            contest.key().as_ref(),
+            artist.key().as_ref()],
+        bump = artwork.nft_vault_authority_bump)]
+    /// CHECK: only used as a signing PDA
+    pub nft_vault_authority: UncheckedAccount<'info>,
+    #[account(mut,
+        token::mint = nft_mint,
+        token::authority = artist)]
+    pub artist_nft_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Artist only, for an entry that was neither approved nor rejected before voting started. Such an
+/// entry can not be voted for, so it is marked rejected and its NFT returned to the artist.
+pub fn handler(ctx: Context<WithdrawArtwork>) -> Result<()> {
+    let now_ts = Clock::get().unwrap().unix_timestamp as u64;
+    require!(
+        ctx.accounts.contest.vote_start_at <= now_ts,
+        ErrorCode::ReviewNotEnded
+    );
+    require!(
+        ctx.accounts.artwork.status == ArtworkStatus::Pending,
+        ErrorCode::ArtworkAlreadyReviewed
+    );
+
+    ctx.accounts.artwork.status = ArtworkStatus::Rejected;
+
+    // return the NFT to the artist
+    let contest_key = ctx.accounts.contest.key();
+    let artist_key = ctx.accounts.artist.key();
+    let authority_seeds = &[
+        NFT_VAULT_AUTHORITY_SEED,
+        contest_key.as_ref(),
+        artist_key.as_ref(),
+        &[ctx.accounts.artwork.nft_vault_authority_bump],
+    ];
+    token::transfer(
+        ctx.accounts
+            .into_transfer_to_artist_context()
+            .with_signer(&[&authority_seeds[..]]),
+        1,
+    )?;
+
+    Ok(())
+}
+
+impl<'info> WithdrawArtwork<'info> {
+    fn into_transfer_to_artist_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.nft_vault_account.to_account_info().clone(),
+            to: self.artist_nft_token_account.to_account_info().clone(),
+            authority: self.nft_vault_authority.to_account_info().clone(),
+        };
+        CpiContext::new(
+            self.token_program.to_account_infos()[0].clone(),
+            cpi_accounts,
+        )
+    }
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{ContestMetadata, LaunchArgs};
 
 pub mod common;
 pub mod instructions;
+pub mod payout;
+pub mod pda;
 pub mod state;
 
 declare_id!("8wRhhgnw55z1QELi6wDoAnKbnYsU9X9U5kZnMQ12vopf");
@@ -15,38 +18,66 @@ pub mod nft_contest {
         instructions::initialize::handler(ctx)
     }
 
-    pub fn launch(
-        ctx: Context<Launch>,
-        prize_amount: u64,
-        percentage_to_artist: u8, // amount is raw number like lamports
-        submit_start_at: u64,
-        submit_end_at: u64,
-        vote_start_at: u64,
-        vote_end_at: u64,
-        title_of_contest: Vec<u8>,
-        link_to_project: Vec<u8>,
-        vec_size: u8,
+    pub fn launch(ctx: Context<Launch>, args: LaunchArgs) -> Result<()> {
+        instructions::launch::handler(ctx, args)
+    }
+
+    pub fn update_contest_metadata(
+        ctx: Context<UpdateContestMetadata>,
+        metadata: ContestMetadata,
+    ) -> Result<()> {
+        instructions::update_contest_metadata::handler(ctx, metadata)
+    }
+
+    pub fn increase_max_artworks(
+        ctx: Context<IncreaseMaxArtworks>,
+        new_max_artworks: u32,
     ) -> Result<()> {
-        instructions::launch::handler(
-            ctx,
-            prize_amount,
-            percentage_to_artist, // amount is raw number like lamports
-            submit_start_at,
-            submit_end_at,
-            vote_start_at,
-            vote_end_at,
-            title_of_contest,
-            link_to_project,
-            vec_size,
-        )
+        instructions::increase_max_artworks::handler(ctx, new_max_artworks)
+    }
+
+    pub fn set_curators(ctx: Context<SetCurators>, curators: Vec<Pubkey>) -> Result<()> {
+        instructions::set_curators::handler(ctx, curators)
+    }
+
+    pub fn cancel_contest(ctx: Context<CancelContest>) -> Result<()> {
+        instructions::cancel_contest::handler(ctx)
     }
 
     pub fn submit(ctx: Context<Submit>) -> Result<()> {
         instructions::submit::handler(ctx)
     }
 
-    pub fn vote(ctx: Context<Vote>, voted_artwork_id: u8) -> Result<()> {
-        instructions::vote::handler(ctx, voted_artwork_id as usize)
+    pub fn approve_artwork(ctx: Context<ApproveArtwork>) -> Result<()> {
+        instructions::approve_artwork::handler(ctx)
+    }
+
+    pub fn reject_artwork(ctx: Context<RejectArtwork>) -> Result<()> {
+        instructions::reject_artwork::handler(ctx)
+    }
+
+    pub fn withdraw_artwork(ctx: Context<WithdrawArtwork>) -> Result<()> {
+        instructions::withdraw_artwork::handler(ctx)
+    }
+
+    pub fn vote(ctx: Context<Vote>, voted_artwork_id: u64) -> Result<()> {
+        instructions::vote::handler(ctx, voted_artwork_id)
+    }
+
+    pub fn score_artwork(ctx: Context<ScoreArtwork>, score: u8) -> Result<()> {
+        instructions::score_artwork::handler(ctx, score)
+    }
+
+    pub fn tally_votes<'info>(ctx: Context<'_, '_, '_, 'info, TallyVotes<'info>>) -> Result<()> {
+        instructions::tally_votes::handler(ctx)
+    }
+
+    pub fn unlock_vote(ctx: Context<UnlockVote>) -> Result<()> {
+        instructions::unlock_vote::handler(ctx)
+    }
+
+    pub fn finalize_contest(ctx: Context<FinalizeContest>) -> Result<()> {
+        instructions::finalize_contest::handler(ctx)
     }
 
     pub fn claim_by_artist(ctx: Context<ClaimByArtist>) -> Result<()> {
@@ -57,7 +88,33 @@ pub mod nft_contest {
         instructions::claim_by_voter::handler(ctx)
     }
 
+    pub fn distribute<'info>(ctx: Context<'_, '_, '_, 'info, Distribute<'info>>) -> Result<()> {
+        instructions::distribute::handler(ctx)
+    }
+
     pub fn claim_by_contest_owner(ctx: Context<ClaimByContestOwner>) -> Result<()> {
         instructions::claim_by_contest_owner::handler(ctx)
     }
+
+    pub fn sweep_remainder(ctx: Context<SweepRemainder>) -> Result<()> {
+        instructions::sweep_remainder::handler(ctx)
+    }
+
+    pub fn migrate_counter(ctx: Context<MigrateCounter>) -> Result<()> {
+        instructions::migrate_counter::handler(ctx)
+    }
+
+    pub fn migrate_contest<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateContest<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_contest::handler(ctx)
+    }
+
+    pub fn migrate_artwork(ctx: Context<MigrateArtwork>) -> Result<()> {
+        instructions::migrate_artwork::handler(ctx)
+    }
+
+    pub fn migrate_vote_data(ctx: Context<MigrateVoteData>) -> Result<()> {
+        instructions::migrate_vote_data::handler(ctx)
+    }
 }
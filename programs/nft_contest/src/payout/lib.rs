@@ -0,0 +1,137 @@
+use crate::common::lib::ErrorCode;
+use anchor_lang::prelude::*;
+
+// Every payout of a contest is computed here. Intermediates are u128 so that no product of two
+// u64 amounts can overflow, and every result is rounded down so payouts never exceed the prize.
+
+/// Amounts frozen by finalize_contest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Payouts {
+    pub artist_payout_per_winner: u64,
+    pub voter_pool_amount: u64,
+    pub voter_payout_per_vote: u64,
+}
+
+/// Splits the prize between `num_of_winners` artists and `rewarded_votes` votes.
+/// Nothing is paid to a side with no winner or no rewarded vote, its share stays in the vault.
+pub fn compute_payouts(
+    prize_amount: u64,
+    percentage_to_artist: u8,
+    num_of_winners: u64,
+    rewarded_votes: u64,
+) -> Result<Payouts> {
+    let artist_pool_amount = percent_of(prize_amount, percentage_to_artist)?;
+    let voter_pool_amount = checked_sub(prize_amount, artist_pool_amount)?;
+    let mut payouts = Payouts {
+        voter_pool_amount,
+        ..Payouts::default()
+    };
+    if num_of_winners > 0 {
+        payouts.artist_payout_per_winner = split(artist_pool_amount, num_of_winners)?;
+    }
+    if rewarded_votes > 0 {
+        payouts.voter_payout_per_vote = split(voter_pool_amount, rewarded_votes)?;
+    }
+    Ok(payouts)
+}
+
+/// `percent` percent of `amount`.
+pub fn percent_of(amount: u64, percent: u8) -> Result<u64> {
+    require!(percent <= 100, ErrorCode::InvalidPercentage);
+    mul_div(amount, percent as u64, 100)
+}
+
+/// Equal share of `amount` between `parts`.
+pub fn split(amount: u64, parts: u64) -> Result<u64> {
+    mul_div(amount, 1, parts)
+}
+
+/// Share of `amount` proportional to `weight` out of `total_weight`.
+pub fn pro_rata(amount: u64, weight: u64, total_weight: u64) -> Result<u64> {
+    require!(weight <= total_weight, ErrorCode::InvalidPayoutWeight);
+    mul_div(amount, weight, total_weight)
+}
+
+/// Part of `amount` released `elapsed` seconds into a linear vesting of `duration` seconds.
+pub fn vested(amount: u64, elapsed: u64, duration: u64) -> Result<u64> {
+    if elapsed >= duration {
+        return Ok(amount);
+    }
+    mul_div(amount, elapsed, duration)
+}
+
+fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::PayoutDivisionByZero);
+    let value = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::PayoutOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(ErrorCode::PayoutDivisionByZero)?;
+    Ok(u64::try_from(value).map_err(|_| ErrorCode::PayoutOverflow)?)
+}
+
+fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    Ok(a.checked_sub(b).ok_or(ErrorCode::PayoutOverflow)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn payouts_never_exceed_prize(
+            prize_amount in any::<u64>(),
+            percentage_to_artist in 0u8..=100,
+            num_of_winners in 0u64..=8,
+            rewarded_votes in 0u64..=u32::MAX as u64,
+        ) {
+            let payouts =
+                compute_payouts(prize_amount, percentage_to_artist, num_of_winners, rewarded_votes)
+                    .unwrap();
+            let artist_total =
+                payouts.artist_payout_per_winner as u128 * num_of_winners as u128;
+            let voter_total = payouts.voter_payout_per_vote as u128 * rewarded_votes as u128;
+            prop_assert!(payouts.voter_pool_amount <= prize_amount);
+            prop_assert!(voter_total <= payouts.voter_pool_amount as u128);
+            prop_assert!(artist_total + voter_total <= prize_amount as u128);
+        }
+
+        #[test]
+        fn weighted_rewards_never_exceed_pool(
+            pool_amount in any::<u64>(),
+            weights in proptest::collection::vec(0..=u64::MAX / 32, 1..32),
+        ) {
+            let total_weight = weights.iter().sum::<u64>();
+            prop_assume!(total_weight > 0);
+            let total_paid = weights
+                .iter()
+                .map(|w| pro_rata(pool_amount, *w, total_weight).unwrap() as u128)
+                .sum::<u128>();
+            prop_assert!(total_paid <= pool_amount as u128);
+        }
+
+        #[test]
+        fn vesting_never_exceeds_amount(
+            amount in any::<u64>(),
+            elapsed in any::<u64>(),
+            later in any::<u64>(),
+            duration in any::<u64>(),
+        ) {
+            let released = vested(amount, elapsed, duration).unwrap();
+            prop_assert!(released <= amount);
+            prop_assert!(released <= vested(amount, elapsed.saturating_add(later), duration).unwrap());
+        }
+    }
+
+    #[test]
+    fn percent_above_100_is_rejected() {
+        assert!(percent_of(100, 101).is_err());
+    }
+
+    #[test]
+    fn split_by_zero_is_rejected() {
+        assert!(split(100, 0).is_err());
+    }
+}
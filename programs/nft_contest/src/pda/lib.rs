@@ -0,0 +1,161 @@
+//! Seeds of every PDA owned or signed for by the program.
+//!
+//! On chain, the bumps found at creation are stored in the accounts and reused with
+//! `create_program_address`, the `find_*` helpers are meant for clients and for the
+//! one time derivation when an account is created.
+use anchor_lang::prelude::*;
+
+pub const COUNTER_SEED: &[u8] = b"counter";
+pub const CONTEST_SEED: &[u8] = b"contest";
+pub const PRIZE_VAULT_SEED: &[u8] = b"prize_vault";
+pub const PRIZE_VAULT_AUTHORITY_SEED: &[u8] = b"prize_vault_authority";
+pub const ARTIST_ENTRIES_SEED: &[u8] = b"artist_entries";
+pub const ARTWORK_SEED: &[u8] = b"artwork";
+pub const NFT_VAULT_SEED: &[u8] = b"nft_vault";
+pub const NFT_VAULT_AUTHORITY_SEED: &[u8] = b"nft_vault_authority";
+pub const VOTE_SEED: &[u8] = b"vote";
+pub const VOTE_ESCROW_SEED: &[u8] = b"vote_escrow";
+pub const JUDGE_SCORE_SEED: &[u8] = b"judge_score";
+pub const OWNER_PROFILE_SEED: &[u8] = b"owner_profile";
+pub const OWNER_CONTEST_PAGE_SEED: &[u8] = b"owner_contest_page";
+
+pub fn find_counter_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COUNTER_SEED], &crate::ID)
+}
+
+pub fn find_contest_address(contest_owner: &Pubkey, contest_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CONTEST_SEED,
+            contest_owner.as_ref(),
+            &contest_id.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_prize_vault_address(contest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRIZE_VAULT_SEED, contest.as_ref()], &crate::ID)
+}
+
+pub fn find_prize_vault_authority_address(contest: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRIZE_VAULT_AUTHORITY_SEED, contest.as_ref()], &crate::ID)
+}
+
+pub fn find_artist_entries_address(contest: &Pubkey, artist: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ARTIST_ENTRIES_SEED, contest.as_ref(), artist.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_artwork_address(contest: &Pubkey, artist: &Pubkey, entry_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ARTWORK_SEED,
+            contest.as_ref(),
+            artist.as_ref(),
+            &entry_index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_nft_vault_address(contest: &Pubkey, artist: &Pubkey, entry_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            NFT_VAULT_SEED,
+            contest.as_ref(),
+            artist.as_ref(),
+            &entry_index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_nft_vault_authority_address(contest: &Pubkey, artist: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NFT_VAULT_AUTHORITY_SEED, contest.as_ref(), artist.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_vote_data_address(contest: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_SEED, contest.as_ref(), voter.as_ref()], &crate::ID)
+}
+
+pub fn find_vote_escrow_address(contest: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_ESCROW_SEED, contest.as_ref(), voter.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_owner_profile_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OWNER_PROFILE_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn find_owner_contest_page_address(owner: &Pubkey, page_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            OWNER_CONTEST_PAGE_SEED,
+            owner.as_ref(),
+            &page_index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_judge_score_address(artwork: &Pubkey, judge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[JUDGE_SCORE_SEED, artwork.as_ref(), judge.as_ref()],
+        &crate::ID,
+    )
+}
+
+// Addresses of the accounts created by the first release, whose seeds numbered contests in
+// decimal and allowed a single artwork per artist. Migrated accounts keep these addresses.
+
+pub fn find_legacy_contest_address(contest_owner: &Pubkey, contest_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CONTEST_SEED,
+            contest_owner.as_ref(),
+            contest_id.to_string().as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_legacy_prize_vault_address(contest_owner: &Pubkey, contest_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PRIZE_VAULT_SEED,
+            contest_owner.as_ref(),
+            contest_id.to_string().as_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_legacy_artwork_address(contest: &Pubkey, artist: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ARTWORK_SEED, contest.as_ref(), artist.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_legacy_nft_vault_address(contest: &Pubkey, artist: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NFT_VAULT_SEED, contest.as_ref(), artist.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// ProgramData account of the program, holding the upgrade authority allowed to migrate accounts.
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[crate::ID.as_ref()],
+        &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )
+}
@@ -0,0 +1,222 @@
+use crate::common::lib::ErrorCode;
+use crate::state::*;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+// Layouts of the accounts created by the first release, before they carried a version. They have
+// the same discriminators as the current ones, and are upgraded in place by the migrate_*
+// instructions. Fields missing from a legacy layout are defaulted by its From impl, the
+// migrate_* handlers then fill those derived from other accounts, like bumps and vote counts.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CounterV0 {
+    pub is_initialized: bool,
+    pub contest_count: u64,
+}
+
+impl From<CounterV0> for Counter {
+    fn from(legacy: CounterV0) -> Self {
+        Self {
+            is_initialized: legacy.is_initialized,
+            contest_count: legacy.contest_count,
+            bump: 0,
+            version: Self::VERSION,
+            reserved: [0; RESERVED_SPACE],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ContestV0 {
+    pub is_initialized: bool,
+    pub contest_id: u64,
+    pub contest_owner: Pubkey,
+    pub prize_amount: u64,
+    pub percentage_to_artist: u8,
+    pub submit_start_at: u64,
+    pub submit_end_at: u64,
+    pub vote_start_at: u64,
+    pub vote_end_at: u64,
+    pub title_of_contest: Vec<u8>,
+    pub link_to_project: Vec<u8>,
+    pub artwork_count: u64,
+    // votes of every artwork, indexed by artwork id
+    pub artworks_vote_counter: Vec<u64>,
+}
+
+impl From<ContestV0> for Contest {
+    fn from(legacy: ContestV0) -> Self {
+        Self {
+            is_initialized: legacy.is_initialized,
+            contest_id: legacy.contest_id,
+            contest_owner: legacy.contest_owner,
+            prize_mint: Pubkey::default(),
+            prize_amount: legacy.prize_amount,
+            percentage_to_artist: legacy.percentage_to_artist,
+            submit_start_at: legacy.submit_start_at,
+            submit_end_at: legacy.submit_end_at,
+            vote_start_at: legacy.vote_start_at,
+            vote_end_at: legacy.vote_end_at,
+            title_of_contest: legacy_string(&legacy.title_of_contest, MAX_TITLE_LEN),
+            link_to_project: legacy_string(&legacy.link_to_project, MAX_LINK_LEN),
+            description: String::new(),
+            cover_image_uri: String::new(),
+            artwork_count: legacy.artwork_count,
+            // the first release had no limits on entries nor reviews
+            max_artworks: u32::MAX,
+            required_collection: None,
+            max_entries_per_artist: 1,
+            curation_enabled: false,
+            curators: vec![],
+            judges: vec![],
+            judge_weight_percent: 0,
+            artist_vesting_duration: 0,
+            // voters of the most voted artwork shared the voter share, with no claim deadline
+            voter_reward_mode: VoterRewardMode::WinnersOnly,
+            claim_end_at: u64::MAX,
+            tally_pass: 0,
+            tallied_artwork_count: 0,
+            total_vote_count: 0,
+            total_vote_weight: 0,
+            winner_score: 0,
+            winning_vote_total: 0,
+            winning_artwork_ids: vec![],
+            is_finalized: false,
+            artist_payout_per_winner: 0,
+            voter_pool_amount: 0,
+            voter_payout_per_vote: 0,
+            bump: 0,
+            prize_vault_bump: 0,
+            prize_vault_authority_bump: 0,
+            version: Self::VERSION,
+            status: ContestStatus::Draft,
+            legacy_seeds: true,
+            reserved: [0; 31],
+            _pad: [0; 31],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArtworkV0 {
+    pub is_initialized: bool,
+    pub artwork_id: u64,
+    pub associated_contest_id: u64,
+    pub artist_key: Pubkey,
+    pub artwork_token_account: Pubkey,
+}
+
+impl From<ArtworkV0> for Artwork {
+    fn from(legacy: ArtworkV0) -> Self {
+        Self {
+            is_initialized: legacy.is_initialized,
+            artwork_id: legacy.artwork_id,
+            associated_contest_id: legacy.associated_contest_id,
+            artist_key: legacy.artist_key,
+            entry_index: 0,
+            artwork_token_account: legacy.artwork_token_account,
+            vote_count: 0,
+            vote_weight_total: 0,
+            judge_score_total: 0,
+            // every submission was votable
+            status: ArtworkStatus::Approved,
+            prize_released_amount: 0,
+            bump: 0,
+            nft_vault_bump: 0,
+            nft_vault_authority_bump: 0,
+            version: Self::VERSION,
+            legacy_seeds: true,
+            reserved: [0; 32],
+            _pad: [0; 31],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VoteDataV0 {
+    pub is_initialized: bool,
+    pub voter_key: Pubkey,
+    pub voted_artwork_id: u64,
+}
+
+impl From<VoteDataV0> for VoteData {
+    fn from(legacy: VoteDataV0) -> Self {
+        Self {
+            is_initialized: legacy.is_initialized,
+            contest: Pubkey::default(),
+            voter_key: legacy.voter_key,
+            voted_artwork_id: legacy.voted_artwork_id,
+            weight: 1,
+            // claims were not recorded
+            reward_claimed: false,
+            bump: 0,
+            version: Self::VERSION,
+            reserved: [0; RESERVED_SPACE],
+        }
+    }
+}
+
+/// UTF-8 text of legacy raw bytes, cut to `max_len` bytes on a char boundary.
+fn legacy_string(bytes: &[u8], max_len: usize) -> String {
+    let mut text = String::from_utf8_lossy(bytes).into_owned();
+    let mut len = text.len().min(max_len);
+    while !text.is_char_boundary(len) {
+        len -= 1;
+    }
+    text.truncate(len);
+    text
+}
+
+/// Decodes `account` in the legacy layout `L` of `T`, failing once it has been migrated.
+pub fn read<L, T>(account: &AccountInfo, space: usize) -> Result<L>
+where
+    L: AnchorDeserialize,
+    T: Discriminator,
+{
+    // current layouts are larger than the legacy ones, by their version and reserved bytes
+    require!(
+        account.data_len() < space,
+        ErrorCode::AccountAlreadyMigrated
+    );
+    let data = account.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == T::discriminator(),
+        ErrorCode::InvalidLegacyAccount
+    );
+    // legacy accounts were sized from the Rust layout, so data may follow the fields
+    L::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::InvalidLegacyAccount.into())
+}
+
+/// Upgrades `account` from the legacy layout `L` to `T` in place with `upgrade`, growing it to
+/// `space` bytes. `admin` tops up the lamports the account needs to stay rent exempt.
+pub fn migrate<'info, L, T>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    admin: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    upgrade: impl FnOnce(L) -> Result<T>,
+) -> Result<()>
+where
+    L: AnchorDeserialize,
+    T: AccountSerialize + Discriminator,
+{
+    let current = upgrade(read::<L, T>(account, space)?)?;
+
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: admin.clone(),
+            to: account.clone(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(system_program.clone(), cpi_accounts),
+            shortfall,
+        )?;
+    }
+    account.realloc(space, true)?;
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    current.try_serialize(&mut writer)
+}
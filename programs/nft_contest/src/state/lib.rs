@@ -1,10 +1,74 @@
+use crate::common::lib::ErrorCode;
+use crate::payout;
 use anchor_lang::prelude::*;
+use solana_safe_math::SafeMath;
+
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_LINK_LEN: usize = 200;
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+pub const MAX_COVER_IMAGE_URI_LEN: usize = 200;
+// tied artworks beyond this number are not recorded as winners, lower artwork ids come first
+pub const MAX_WINNERS: usize = 8;
+pub const CONTESTS_PER_PAGE: usize = 32;
+pub const MAX_CURATORS: usize = 5;
+pub const MAX_JUDGES: usize = 10;
+pub const MAX_JUDGE_SCORE: u8 = 10;
+// zeroed bytes at the end of versioned accounts, for fields added by later layouts
+pub const RESERVED_SPACE: usize = 64;
 
 #[repr(C)]
 #[account]
 pub struct Counter {
     pub is_initialized: bool,
     pub contest_count: u64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; RESERVED_SPACE],
+}
+
+impl Counter {
+    pub const VERSION: u8 = 1;
+
+    pub const SPACE: usize = 8 // discriminator
+        + 1 // is_initialized
+        + 8 // contest_count
+        + 1 // bump
+        + 1 // version
+        + RESERVED_SPACE; // reserved
+}
+
+/// One per contest owner, created by their first launch.
+#[account]
+pub struct OwnerProfile {
+    pub owner: Pubkey,
+    pub contest_count: u64,
+    pub bump: u8,
+}
+
+impl OwnerProfile {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // owner
+        + 8 // contest_count
+        + 1; // bump
+}
+
+/// Keys of an owner's contests in launch order. Contest number `n` of an owner is stored in
+/// page `n / CONTESTS_PER_PAGE`.
+#[account]
+pub struct OwnerContestPage {
+    pub owner: Pubkey,
+    pub page_index: u64,
+    pub contest_keys: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl OwnerContestPage {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // owner
+        + 8 // page_index
+        + 4
+        + 32 * CONTESTS_PER_PAGE // contest_keys
+        + 1; // bump
 }
 
 #[repr(C)]
@@ -13,16 +77,419 @@ pub struct Contest {
     pub is_initialized: bool,
     pub contest_id: u64,
     pub contest_owner: Pubkey,
+    pub prize_mint: Pubkey,
     pub prize_amount: u64,
     pub percentage_to_artist: u8,
     pub submit_start_at: u64,
     pub submit_end_at: u64,
     pub vote_start_at: u64,
     pub vote_end_at: u64,
-    pub title_of_contest: Vec<u8>,
-    pub link_to_project: Vec<u8>,
+    pub title_of_contest: String,
+    pub link_to_project: String,
+    pub description: String,
+    pub cover_image_uri: String,
     pub artwork_count: u64,
-    pub artworks_vote_counter: Vec<u64>,
+    pub max_artworks: u32,
+    // when set, only NFTs of this verified Metaplex collection can be submitted
+    pub required_collection: Option<Pubkey>,
+    pub max_entries_per_artist: u8,
+    // when enabled, artworks must be approved between submit_end_at and vote_start_at to be votable
+    pub curation_enabled: bool,
+    pub curators: Vec<Pubkey>,
+    // judges score artworks, their normalized scores weigh judge_weight_percent against public votes
+    pub judges: Vec<Pubkey>,
+    pub judge_weight_percent: u8,
+    // artist prizes are released linearly over this many seconds after vote_end_at, 0 pays at once
+    pub artist_vesting_duration: u64,
+    pub voter_reward_mode: VoterRewardMode,
+    // prizes can be claimed until then, after which the owner may sweep the prize vault
+    pub claim_end_at: u64,
+    // written by tally_votes once voting has ended
+    pub tally_pass: u8,
+    pub tallied_artwork_count: u64,
+    pub total_vote_count: u64,
+    pub total_vote_weight: u64,
+    pub winner_score: u128,
+    pub winning_vote_total: u64,
+    pub winning_artwork_ids: Vec<u64>,
+    // written by finalize_contest
+    pub is_finalized: bool,
+    pub artist_payout_per_winner: u64,
+    pub voter_pool_amount: u64,
+    pub voter_payout_per_vote: u64,
+    pub bump: u8,
+    pub prize_vault_bump: u8,
+    pub prize_vault_authority_bump: u8,
+    // layout of the account, accounts created before it was versioned go through migrate_contest
+    pub version: u8,
+    // stage stored by the last instruction writing the contest. Draft, Open and Voting follow the
+    // clock, and vote leaves the contest read only, so the stored value can lag behind the
+    // schedule: clients must read current_status(now) rather than this field
+    pub status: ContestStatus,
+    // set by migrate_contest, the contest and its prize vault keep the PDAs of the first release
+    pub legacy_seeds: bool,
+    // RESERVED_SPACE minus the status and legacy_seeds bytes, split in two as borsh has no impl
+    // for [u8; 62]
+    pub reserved: [u8; 31],
+    pub _pad: [u8; 31],
+}
+
+impl Contest {
+    pub const VERSION: u8 = 1;
+
+    // byte offsets in the account data, for getProgramAccounts memcmp filters
+    pub const CONTEST_ID_OFFSET: usize = 8 + 1;
+    pub const CONTEST_OWNER_OFFSET: usize = Self::CONTEST_ID_OFFSET + 8;
+
+    // borsh size of every field, including the discriminator
+    pub const SPACE: usize = 8 // discriminator
+        + 1 // is_initialized
+        + 8 // contest_id
+        + 32 // contest_owner
+        + 32 // prize_mint
+        + 8 // prize_amount
+        + 1 // percentage_to_artist
+        + 8 * 4 // submit_start_at, submit_end_at, vote_start_at, vote_end_at
+        + ContestMetadata::MAX_SPACE
+        + 8 // artwork_count
+        + 4 // max_artworks
+        + 1
+        + 32 // required_collection
+        + 1 // max_entries_per_artist
+        + 1 // curation_enabled
+        + 4
+        + 32 * MAX_CURATORS // curators
+        + 4
+        + 32 * MAX_JUDGES // judges
+        + 1 // judge_weight_percent
+        + 8 // artist_vesting_duration
+        + 1 // voter_reward_mode
+        + 8 // claim_end_at
+        + 1 // tally_pass
+        + 8 * 3 // tallied_artwork_count, total_vote_count, total_vote_weight
+        + 16 // winner_score
+        + 8 // winning_vote_total
+        + 4
+        + 8 * MAX_WINNERS // winning_artwork_ids
+        + 1 // is_finalized
+        + 8 * 3 // artist_payout_per_winner, voter_pool_amount, voter_payout_per_vote
+        + 3 // bump, prize_vault_bump, prize_vault_authority_bump
+        + 1 // version
+        + 1 // status
+        + 1 // legacy_seeds
+        + 31 // reserved
+        + 31; // _pad
+
+    /// Last seed of the contest PDA: the contest id in little endian, or in decimal text for
+    /// contests of the first release.
+    pub fn id_seed(&self) -> Vec<u8> {
+        if self.legacy_seeds {
+            self.contest_id.to_string().into_bytes()
+        } else {
+            self.contest_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Seeds of the prize vault PDA after PRIZE_VAULT_SEED: the contest address, or the owner and
+    /// the id seed for contests of the first release. The second seed of a current contest is
+    /// empty, which leaves its address unchanged.
+    pub fn prize_vault_seeds(&self, contest: &Pubkey) -> [Vec<u8>; 2] {
+        if self.legacy_seeds {
+            [self.contest_owner.to_bytes().to_vec(), self.id_seed()]
+        } else {
+            [contest.to_bytes().to_vec(), vec![]]
+        }
+    }
+
+    /// Mixing judge scores with votes needs the total of votes, which takes a first tally pass.
+    pub fn needs_vote_total(&self) -> bool {
+        self.judge_weight_percent > 0 && self.judge_weight_percent < 100
+    }
+
+    pub fn last_tally_pass(&self) -> u8 {
+        if self.needs_vote_total() {
+            1
+        } else {
+            0
+        }
+    }
+
+    pub fn is_tallied(&self) -> bool {
+        self.tally_pass == self.last_tally_pass()
+            && self.tallied_artwork_count == self.artwork_count
+    }
+
+    /// Adds the next artwork, in order of artwork id, to the current tally pass.
+    pub fn tally_artwork(&mut self, artwork: &Artwork) -> Result<()> {
+        if artwork.status == ArtworkStatus::Approved {
+            // votes are summed in the first pass, scores may depend on the sum
+            if self.tally_pass == 0 {
+                self.total_vote_count = self.total_vote_count.safe_add(artwork.vote_count)?;
+                self.total_vote_weight =
+                    self.total_vote_weight.safe_add(artwork.vote_weight_total)?;
+            }
+            // keep every approved artwork sharing the best score, up to MAX_WINNERS
+            if self.tally_pass == self.last_tally_pass() {
+                let score = self.artwork_score(artwork);
+                if score > self.winner_score {
+                    self.winner_score = score;
+                    self.winning_vote_total = artwork.vote_count;
+                    self.winning_artwork_ids = vec![artwork.artwork_id];
+                } else if score == self.winner_score && self.winning_artwork_ids.len() < MAX_WINNERS
+                {
+                    self.winning_vote_total =
+                        self.winning_vote_total.safe_add(artwork.vote_count)?;
+                    self.winning_artwork_ids.push(artwork.artwork_id);
+                }
+            }
+        }
+        self.tallied_artwork_count += 1;
+        Ok(())
+    }
+
+    /// Ends a tally_votes transaction: starts the scoring pass from the first artwork once every
+    /// vote is summed.
+    pub fn advance_tally_pass(&mut self) {
+        if self.tally_pass < self.last_tally_pass()
+            && self.tallied_artwork_count == self.artwork_count
+        {
+            self.tally_pass += 1;
+            self.tallied_artwork_count = 0;
+        }
+    }
+
+    /// Freezes the winners and the amount every claim pays out, once every artwork is tallied.
+    pub fn finalize(&mut self) -> Result<()> {
+        require!(!self.is_finalized, ErrorCode::ContestAlreadyFinalized);
+        require!(self.is_tallied(), ErrorCode::VotesNotTallied);
+
+        // nobody wins a contest without votes or judge points
+        if self.winner_score == 0 {
+            self.winning_artwork_ids.clear();
+            self.winning_vote_total = 0;
+        }
+
+        // the artist share is split between tied winners,
+        // the voter share according to the voter reward mode
+        let num_of_winners = self.winning_artwork_ids.len() as u64;
+        let rewarded_votes = match self.voter_reward_mode {
+            VoterRewardMode::WinnersOnly => self.winning_vote_total,
+            VoterRewardMode::AllVoters => self.total_vote_count,
+            // weighted rewards are computed per vote when claiming
+            VoterRewardMode::VoteWeighted | VoterRewardMode::None => 0,
+        };
+        let payouts = payout::compute_payouts(
+            self.prize_amount,
+            self.percentage_to_artist,
+            num_of_winners,
+            rewarded_votes,
+        )?;
+        self.artist_payout_per_winner = payouts.artist_payout_per_winner;
+        self.voter_pool_amount = payouts.voter_pool_amount;
+        self.voter_payout_per_vote = payouts.voter_payout_per_vote;
+        self.is_finalized = true;
+        self.status = ContestStatus::Finalized;
+        Ok(())
+    }
+
+    /// Part of a winner's artist prize released at `now_ts`.
+    pub fn artist_vested_amount(&self, now_ts: u64) -> Result<u64> {
+        payout::vested(
+            self.artist_payout_per_winner,
+            now_ts.saturating_sub(self.vote_end_at),
+            self.artist_vesting_duration,
+        )
+    }
+
+    /// Prize paid for a vote, according to the voter reward mode of the contest.
+    pub fn voter_reward(&self, vote_data: &VoteData) -> Result<u64> {
+        match self.voter_reward_mode {
+            VoterRewardMode::WinnersOnly => {
+                if self.is_winner(vote_data.voted_artwork_id) {
+                    Ok(self.voter_payout_per_vote)
+                } else {
+                    Ok(0)
+                }
+            }
+            VoterRewardMode::AllVoters => Ok(self.voter_payout_per_vote),
+            VoterRewardMode::VoteWeighted => {
+                if self.total_vote_weight == 0 {
+                    return Ok(0);
+                }
+                payout::pro_rata(
+                    self.voter_pool_amount,
+                    vote_data.weight,
+                    self.total_vote_weight,
+                )
+            }
+            VoterRewardMode::None => Ok(0),
+        }
+    }
+
+    /// Weighted sum of normalized judge points and normalized votes,
+    /// w * J / (judges * MAX_JUDGE_SCORE) + (100 - w) * V / total votes,
+    /// multiplied by both denominators to compare artworks in integers.
+    pub fn artwork_score(&self, artwork: &Artwork) -> u128 {
+        let judge_weight = self.judge_weight_percent as u128;
+        let max_judge_points = (self.judges.len() as u128 * MAX_JUDGE_SCORE as u128).max(1);
+        let total_votes = if self.needs_vote_total() {
+            (self.total_vote_count as u128).max(1)
+        } else {
+            1
+        };
+        judge_weight * artwork.judge_score_total as u128 * total_votes
+            + (100 - judge_weight) * artwork.vote_count as u128 * max_judge_points
+    }
+
+    pub fn is_winner(&self, artwork_id: u64) -> bool {
+        self.winning_artwork_ids.contains(&artwork_id)
+    }
+
+    pub fn is_reviewer(&self, key: &Pubkey) -> bool {
+        self.contest_owner == *key || self.curators.contains(key)
+    }
+
+    /// Status at `now_ts`. Submission and voting open with the clock, not with an instruction, so
+    /// the stored status is moved forward by the schedule. Contests written before the status
+    /// was stored read Draft, and finalized ones are caught up by `is_finalized`.
+    pub fn current_status(&self, now_ts: u64) -> ContestStatus {
+        match self.status {
+            ContestStatus::Draft | ContestStatus::Open | ContestStatus::Voting => {
+                if self.is_finalized {
+                    ContestStatus::Finalized
+                } else if self.vote_start_at <= now_ts {
+                    ContestStatus::Voting
+                } else if self.submit_start_at <= now_ts {
+                    ContestStatus::Open
+                } else {
+                    ContestStatus::Draft
+                }
+            }
+            status => status,
+        }
+    }
+
+    /// Stores the status at `now_ts`, for handlers writing the contest anyway.
+    pub fn advance_status(&mut self, now_ts: u64) {
+        self.status = self.current_status(now_ts);
+    }
+
+    pub fn require_status(&self, now_ts: u64, allowed: &[ContestStatus]) -> Result<()> {
+        require!(
+            allowed.contains(&self.current_status(now_ts)),
+            ErrorCode::InvalidContestStatus
+        );
+        Ok(())
+    }
+
+    pub fn set_metadata(&mut self, metadata: ContestMetadata) {
+        self.title_of_contest = metadata.title_of_contest;
+        self.link_to_project = metadata.link_to_project;
+        self.description = metadata.description;
+        self.cover_image_uri = metadata.cover_image_uri;
+    }
+}
+
+/// Human readable information about a contest, passed to `launch` and `update_contest_metadata`.
+/// Every field is UTF-8 and bounded by the MAX_*_LEN constants (in bytes).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ContestMetadata {
+    pub title_of_contest: String,
+    pub link_to_project: String,
+    pub description: String,
+    pub cover_image_uri: String,
+}
+
+impl ContestMetadata {
+    // each string is serialized as a 4 bytes length prefix followed by its bytes
+    pub const MAX_SPACE: usize = 4
+        + MAX_TITLE_LEN
+        + 4
+        + MAX_LINK_LEN
+        + 4
+        + MAX_DESCRIPTION_LEN
+        + 4
+        + MAX_COVER_IMAGE_URI_LEN;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.title_of_contest.is_empty(), ErrorCode::EmptyTitle);
+        require!(
+            self.title_of_contest.len() <= MAX_TITLE_LEN,
+            ErrorCode::TitleTooLong
+        );
+        require!(
+            self.link_to_project.len() <= MAX_LINK_LEN,
+            ErrorCode::LinkTooLong
+        );
+        require!(
+            self.description.len() <= MAX_DESCRIPTION_LEN,
+            ErrorCode::DescriptionTooLong
+        );
+        require!(
+            self.cover_image_uri.len() <= MAX_COVER_IMAGE_URI_LEN,
+            ErrorCode::CoverImageUriTooLong
+        );
+        Ok(())
+    }
+}
+
+/// Everything `launch` takes besides its accounts, passed as a single instruction argument.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LaunchArgs {
+    // amount is raw number like lamports
+    pub prize_amount: u64,
+    pub percentage_to_artist: u8,
+    pub submit_start_at: u64,
+    pub submit_end_at: u64,
+    pub vote_start_at: u64,
+    pub vote_end_at: u64,
+    pub metadata: ContestMetadata,
+    pub max_artworks: u32,
+    pub required_collection: Option<Pubkey>,
+    pub max_entries_per_artist: u8,
+    pub curation_enabled: bool,
+    pub judges: Vec<Pubkey>,
+    pub judge_weight_percent: u8,
+    pub artist_vesting_duration: u64,
+    pub voter_reward_mode: VoterRewardMode,
+    pub claim_end_at: u64,
+}
+
+/// How the voter share of the prize is split between voters.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoterRewardMode {
+    // voters of the winning artworks, equally per vote
+    WinnersOnly,
+    // every voter, equally per vote
+    AllVoters,
+    // every voter, proportionally to their prize token balance when voting
+    VoteWeighted,
+    // no voter reward, the whole prize goes to artists
+    None,
+}
+
+/// Lifecycle of a contest.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContestStatus {
+    // launched, before submit_start_at
+    Draft,
+    // taking submissions, then reviewing them until vote_start_at
+    Open,
+    // from vote_start_at until finalize_contest, including the tally, submission may overlap it
+    Voting,
+    // winners and payouts are frozen, prizes can be claimed
+    Finalized,
+    // cancelled by its owner before any submission, the prize was refunded
+    Cancelled,
+    // the prize vault was swept and closed after the claim period
+    Settled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArtworkStatus {
+    Pending,
+    Approved,
+    Rejected,
 }
 
 #[repr(C)]
@@ -32,13 +499,222 @@ pub struct Artwork {
     pub artwork_id: u64,
     pub associated_contest_id: u64,
     pub artist_key: Pubkey,
+    // n-th artwork the artist submitted to the contest, starting from 0
+    pub entry_index: u8,
     pub artwork_token_account: Pubkey,
+    pub vote_count: u64,
+    pub vote_weight_total: u64,
+    pub judge_score_total: u64,
+    pub status: ArtworkStatus,
+    // artist prize already paid out to the artist of a winning artwork
+    pub prize_released_amount: u64,
+    pub bump: u8,
+    pub nft_vault_bump: u8,
+    pub nft_vault_authority_bump: u8,
+    pub version: u8,
+    // set by migrate_artwork, the artwork and its NFT vault keep the PDAs of the first release
+    pub legacy_seeds: bool,
+    // RESERVED_SPACE minus the legacy_seeds byte, split in two as borsh has no impl for [u8; 63]
+    pub reserved: [u8; 32],
+    pub _pad: [u8; 31],
+}
+
+impl Artwork {
+    pub const VERSION: u8 = 1;
+
+    // byte offsets in the account data, for getProgramAccounts memcmp filters
+    pub const ARTWORK_ID_OFFSET: usize = 8 + 1;
+    pub const CONTEST_ID_OFFSET: usize = Self::ARTWORK_ID_OFFSET + 8;
+    pub const ARTIST_OFFSET: usize = Self::CONTEST_ID_OFFSET + 8;
+
+    pub const SPACE: usize = 8 // discriminator
+        + 1 // is_initialized
+        + 8 // artwork_id
+        + 8 // associated_contest_id
+        + 32 // artist_key
+        + 1 // entry_index
+        + 32 // artwork_token_account
+        + 8 * 3 // vote_count, vote_weight_total, judge_score_total
+        + 1 // status
+        + 8 // prize_released_amount
+        + 3 // bump, nft_vault_bump, nft_vault_authority_bump
+        + 1 // version
+        + 1 // legacy_seeds
+        + 32 // reserved
+        + 31; // _pad
+
+    /// Last seed of the artwork and NFT vault PDAs: the entry index, or an empty seed for
+    /// artworks of the first release, which allowed one artwork per artist.
+    pub fn entry_seed(&self) -> Vec<u8> {
+        if self.legacy_seeds {
+            vec![]
+        } else {
+            self.entry_index.to_le_bytes().to_vec()
+        }
+    }
+}
+
+/// Number of artworks an artist submitted to a contest.
+#[account]
+pub struct ArtistEntries {
+    pub contest: Pubkey,
+    pub artist: Pubkey,
+    pub entry_count: u8,
+    pub bump: u8,
+}
+
+impl ArtistEntries {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // contest
+        + 32 // artist
+        + 1 // entry_count
+        + 1; // bump
+}
+
+/// Score given by a judge to an artwork, its existence prevents scoring twice.
+#[account]
+pub struct JudgeScore {
+    pub artwork: Pubkey,
+    pub judge: Pubkey,
+    pub score: u8,
+    pub bump: u8,
+}
+
+impl JudgeScore {
+    pub const SPACE: usize = 8 // discriminator
+        + 32 // artwork
+        + 32 // judge
+        + 1 // score
+        + 1; // bump
 }
 
 #[repr(C)]
 #[account]
 pub struct VoteData {
     pub is_initialized: bool,
+    pub contest: Pubkey,
     pub voter_key: Pubkey,
     pub voted_artwork_id: u64,
+    pub weight: u64,
+    pub reward_claimed: bool,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; RESERVED_SPACE],
+}
+
+impl VoteData {
+    pub const VERSION: u8 = 1;
+
+    // byte offsets in the account data, for getProgramAccounts memcmp filters
+    pub const CONTEST_OFFSET: usize = 8 + 1;
+    pub const VOTER_OFFSET: usize = Self::CONTEST_OFFSET + 32;
+    pub const VOTED_ARTWORK_ID_OFFSET: usize = Self::VOTER_OFFSET + 32;
+
+    pub const SPACE: usize = 8 // discriminator
+        + 1 // is_initialized
+        + 32 // contest
+        + 32 // voter_key
+        + 8 // voted_artwork_id
+        + 8 // weight
+        + 1 // reward_claimed
+        + 1 // bump
+        + 1 // version
+        + RESERVED_SPACE; // reserved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zeroed<T: AccountDeserialize>() -> T {
+        T::try_deserialize_unchecked(&mut &[0u8; Contest::SPACE][..]).unwrap()
+    }
+
+    fn serialized<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn status_follows_schedule_until_stored_otherwise() {
+        let mut contest: Contest = zeroed();
+        contest.submit_start_at = 10;
+        contest.submit_end_at = 20;
+        contest.vote_start_at = 30;
+        contest.vote_end_at = 40;
+        assert_eq!(contest.current_status(9), ContestStatus::Draft);
+        assert_eq!(contest.current_status(10), ContestStatus::Open);
+        assert_eq!(contest.current_status(30), ContestStatus::Voting);
+        assert_eq!(contest.current_status(50), ContestStatus::Voting);
+
+        // contests finalized before the status was stored
+        contest.is_finalized = true;
+        assert_eq!(contest.current_status(50), ContestStatus::Finalized);
+
+        contest.status = ContestStatus::Cancelled;
+        assert_eq!(contest.current_status(50), ContestStatus::Cancelled);
+        assert!(contest.require_status(50, &[ContestStatus::Open]).is_err());
+    }
+
+    #[test]
+    fn space_fits_largest_serialized_accounts() {
+        assert_eq!(serialized(&zeroed::<Counter>()).len(), Counter::SPACE);
+        assert_eq!(serialized(&zeroed::<Artwork>()).len(), Artwork::SPACE);
+        assert_eq!(serialized(&zeroed::<VoteData>()).len(), VoteData::SPACE);
+
+        let mut contest: Contest = zeroed();
+        contest.set_metadata(ContestMetadata {
+            title_of_contest: "t".repeat(MAX_TITLE_LEN),
+            link_to_project: "l".repeat(MAX_LINK_LEN),
+            description: "d".repeat(MAX_DESCRIPTION_LEN),
+            cover_image_uri: "c".repeat(MAX_COVER_IMAGE_URI_LEN),
+        });
+        contest.required_collection = Some(Pubkey::new_unique());
+        contest.curators = vec![Pubkey::new_unique(); MAX_CURATORS];
+        contest.judges = vec![Pubkey::new_unique(); MAX_JUDGES];
+        contest.winning_artwork_ids = vec![0; MAX_WINNERS];
+        assert_eq!(serialized(&contest).len(), Contest::SPACE);
+    }
+
+    #[test]
+    fn memcmp_offsets_match_serialized_fields() {
+        let owner = Pubkey::new_unique();
+        let mut contest: Contest = zeroed();
+        contest.contest_id = 7;
+        contest.contest_owner = owner;
+        let data = serialized(&contest);
+        assert_eq!(data[Contest::CONTEST_ID_OFFSET..][..8], 7u64.to_le_bytes());
+        assert_eq!(
+            data[Contest::CONTEST_OWNER_OFFSET..][..32],
+            owner.to_bytes()
+        );
+
+        let artist = Pubkey::new_unique();
+        let mut artwork: Artwork = zeroed();
+        artwork.artwork_id = 3;
+        artwork.associated_contest_id = 7;
+        artwork.artist_key = artist;
+        let data = serialized(&artwork);
+        assert_eq!(data[Artwork::ARTWORK_ID_OFFSET..][..8], 3u64.to_le_bytes());
+        assert_eq!(data[Artwork::CONTEST_ID_OFFSET..][..8], 7u64.to_le_bytes());
+        assert_eq!(data[Artwork::ARTIST_OFFSET..][..32], artist.to_bytes());
+
+        let contest_key = Pubkey::new_unique();
+        let voter = Pubkey::new_unique();
+        let mut vote: VoteData = zeroed();
+        vote.contest = contest_key;
+        vote.voter_key = voter;
+        vote.voted_artwork_id = 3;
+        let data = serialized(&vote);
+        assert_eq!(
+            data[VoteData::CONTEST_OFFSET..][..32],
+            contest_key.to_bytes()
+        );
+        assert_eq!(data[VoteData::VOTER_OFFSET..][..32], voter.to_bytes());
+        assert_eq!(
+            data[VoteData::VOTED_ARTWORK_ID_OFFSET..][..8],
+            3u64.to_le_bytes()
+        );
+    }
 }
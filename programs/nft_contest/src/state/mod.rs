@@ -1,3 +1,4 @@
+pub mod legacy;
 pub mod lib;
 
 pub use lib::*;
@@ -0,0 +1,313 @@
+//! Contest fixture walked through its phases by the program tests: one owner, one artist with two
+//! entries, two voters, a curator and a judge.
+
+use super::*;
+use nft_contest::state::{ContestMetadata, VoterRewardMode};
+use nft_contest_client::instructions::{self, LaunchArgs};
+use nft_contest_client::pda;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+use solana_sdk::transport::TransportError;
+
+pub const T0: i64 = 1_700_000_000;
+pub const SUBMIT_AT: i64 = T0 + 10;
+pub const REVIEW_AT: i64 = T0 + 150;
+pub const VOTE_AT: i64 = T0 + 250;
+pub const TALLY_AT: i64 = T0 + 310;
+pub const SUBMIT_END_AT: u64 = T0 as u64 + 100;
+pub const VOTE_START_AT: u64 = T0 as u64 + 200;
+pub const VOTE_END_AT: u64 = T0 as u64 + 300;
+pub const CLAIM_END_AT: u64 = T0 as u64 + 1_000;
+pub const PRIZE_AMOUNT: u64 = 1_000_000;
+pub const PERCENTAGE_TO_ARTIST: u8 = 70;
+
+pub struct Env {
+    pub bank: Bank,
+    pub owner: Keypair,
+    pub artist: Keypair,
+    pub voters: [Keypair; 2],
+    pub curator: Keypair,
+    pub judge: Keypair,
+    pub prize_mint: Pubkey,
+    pub owner_prize_account: Pubkey,
+    pub artist_prize_account: Pubkey,
+    pub voter_prize_accounts: [Pubkey; 2],
+    pub nft_mints: [Pubkey; 2],
+    pub artist_nft_accounts: [Pubkey; 2],
+    pub owner_nft_account: Pubkey,
+    pub contest: Pubkey,
+    pub artworks: [Pubkey; 2],
+}
+
+pub async fn setup() -> Env {
+    let mut program_test = program_test();
+    let owner = add_user(&mut program_test);
+    let artist = add_user(&mut program_test);
+    let voters = [add_user(&mut program_test), add_user(&mut program_test)];
+    let curator = add_user(&mut program_test);
+    let judge = add_user(&mut program_test);
+
+    // the owner can fund two contests, voters hold prize tokens weighing their votes
+    let prize_mint = add_mint(
+        &mut program_test,
+        Some(owner.pubkey()),
+        6,
+        2 * PRIZE_AMOUNT + 400,
+    );
+    let owner_prize_account = add_token_account(
+        &mut program_test,
+        &prize_mint,
+        &owner.pubkey(),
+        2 * PRIZE_AMOUNT,
+    );
+    let artist_prize_account =
+        add_token_account(&mut program_test, &prize_mint, &artist.pubkey(), 0);
+    let voter_prize_accounts = [
+        add_token_account(&mut program_test, &prize_mint, &voters[0].pubkey(), 300),
+        add_token_account(&mut program_test, &prize_mint, &voters[1].pubkey(), 100),
+    ];
+
+    let mut nft_mints = [Pubkey::default(); 2];
+    let mut artist_nft_accounts = [Pubkey::default(); 2];
+    for i in 0..2 {
+        nft_mints[i] = add_mint(&mut program_test, None, 0, 1);
+        artist_nft_accounts[i] =
+            add_token_account(&mut program_test, &nft_mints[i], &artist.pubkey(), 1);
+        add_nft_metadata(&mut program_test, &nft_mints[i], &artist.pubkey());
+    }
+    let owner_nft_account = add_token_account(&mut program_test, &nft_mints[0], &owner.pubkey(), 0);
+
+    let contest = pda::find_contest_address(&owner.pubkey(), 0).0;
+    let artworks = [
+        pda::find_artwork_address(&contest, &artist.pubkey(), 0).0,
+        pda::find_artwork_address(&contest, &artist.pubkey(), 1).0,
+    ];
+    Env {
+        bank: Bank::start(program_test, T0).await,
+        owner,
+        artist,
+        voters,
+        curator,
+        judge,
+        prize_mint,
+        owner_prize_account,
+        artist_prize_account,
+        voter_prize_accounts,
+        nft_mints,
+        artist_nft_accounts,
+        owner_nft_account,
+        contest,
+        artworks,
+    }
+}
+
+pub fn assert_error(result: TxResult, code: impl Into<u32>) {
+    let code = code.into();
+    match result {
+        Err(TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code),
+        other => panic!("expected custom error {}, got {:?}", code, other),
+    }
+}
+
+/// Curated contest judged half by the judge and half by public votes.
+pub fn launch_args(env: &Env) -> LaunchArgs {
+    LaunchArgs {
+        prize_amount: PRIZE_AMOUNT,
+        percentage_to_artist: PERCENTAGE_TO_ARTIST,
+        submit_start_at: T0 as u64,
+        submit_end_at: SUBMIT_END_AT,
+        vote_start_at: VOTE_START_AT,
+        vote_end_at: VOTE_END_AT,
+        metadata: ContestMetadata {
+            title_of_contest: "Test Contest".to_string(),
+            link_to_project: "https://example.com".to_string(),
+            description: "Contest run by program tests".to_string(),
+            cover_image_uri: "https://arweave.net/cover".to_string(),
+        },
+        max_artworks: 2,
+        required_collection: None,
+        max_entries_per_artist: 2,
+        curation_enabled: true,
+        judges: vec![env.judge.pubkey()],
+        judge_weight_percent: 50,
+        artist_vesting_duration: 0,
+        voter_reward_mode: VoterRewardMode::WinnersOnly,
+        claim_end_at: CLAIM_END_AT,
+    }
+}
+
+pub fn update_metadata_instruction(
+    contest: &Pubkey,
+    contest_owner: &Pubkey,
+    title: &str,
+) -> Instruction {
+    ix(
+        nft_contest::accounts::UpdateContestMetadata {
+            contest_owner: *contest_owner,
+            contest: *contest,
+        },
+        nft_contest::instruction::UpdateContestMetadata {
+            metadata: ContestMetadata {
+                title_of_contest: title.to_string(),
+                ..ContestMetadata::default()
+            },
+        },
+    )
+}
+
+pub fn approve_instruction(env: &Env, reviewer: &Pubkey, artwork: &Pubkey) -> Instruction {
+    instructions::approve_artwork(reviewer, &env.contest, artwork)
+}
+
+pub fn reject_instruction(env: &Env, entry_index: u8) -> Instruction {
+    instructions::reject_artwork(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.artist.pubkey(),
+        entry_index,
+        &env.nft_mints[entry_index as usize],
+        &env.artist_nft_accounts[entry_index as usize],
+    )
+}
+
+pub fn withdraw_instruction(env: &Env, entry_index: u8) -> Instruction {
+    instructions::withdraw_artwork(
+        &env.artist.pubkey(),
+        &env.contest,
+        entry_index,
+        &env.nft_mints[entry_index as usize],
+        &env.artist_nft_accounts[entry_index as usize],
+    )
+}
+
+pub fn score_instruction(env: &Env, judge: &Pubkey, artwork: &Pubkey, score: u8) -> Instruction {
+    ix(
+        nft_contest::accounts::ScoreArtwork {
+            judge: *judge,
+            contest: env.contest,
+            artwork: *artwork,
+            judge_score: pda::find_judge_score_address(artwork, judge).0,
+            system_program: solana_sdk::system_program::ID,
+        },
+        nft_contest::instruction::ScoreArtwork { score },
+    )
+}
+
+pub fn vote_instruction(env: &Env, voter: usize, artwork: usize) -> Instruction {
+    instructions::vote(
+        &env.voters[voter].pubkey(),
+        &env.contest,
+        &env.artworks[artwork],
+        artwork as u64,
+        &env.prize_mint,
+        &env.voter_prize_accounts[voter],
+    )
+}
+
+pub fn claim_by_artist_instruction(env: &Env, entry_index: u8) -> Instruction {
+    instructions::claim_by_artist(
+        &env.artist.pubkey(),
+        &env.contest,
+        entry_index,
+        &env.prize_mint,
+        &env.artist_prize_account,
+    )
+}
+
+pub fn claim_by_voter_instruction(env: &Env, voter: usize) -> Instruction {
+    instructions::claim_by_voter(
+        &env.voters[voter].pubkey(),
+        &env.contest,
+        &env.artworks[0],
+        &env.prize_mint,
+        &env.voter_prize_accounts[voter],
+    )
+}
+
+pub fn sweep_instruction(env: &Env) -> Instruction {
+    instructions::sweep_remainder(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.prize_mint,
+        &env.owner_prize_account,
+    )
+}
+
+pub async fn initialize_and_launch(env: &mut Env) {
+    let program_owner = env.bank.context.payer.pubkey();
+    env.bank
+        .send(&[instructions::initialize(&program_owner)], &[])
+        .await
+        .unwrap();
+    let launch_ix = instructions::launch(
+        &env.owner.pubkey(),
+        0,
+        0,
+        &env.prize_mint,
+        &env.owner_prize_account,
+        launch_args(env),
+    );
+    env.bank.send(&[launch_ix], &[&env.owner]).await.unwrap();
+}
+
+pub async fn submit_entries(env: &mut Env) {
+    env.bank.now = SUBMIT_AT;
+    for entry_index in 0..2 {
+        let submit_ix = instructions::submit(
+            &env.artist.pubkey(),
+            &env.contest,
+            entry_index,
+            &env.nft_mints[entry_index as usize],
+            &env.artist_nft_accounts[entry_index as usize],
+        );
+        env.bank.send(&[submit_ix], &[&env.artist]).await.unwrap();
+    }
+}
+
+/// The curator approves the first artwork, the owner rejects the second one.
+pub async fn review_entries(env: &mut Env) {
+    env.bank.now = REVIEW_AT;
+    let set_curators_ix = instructions::set_curators(
+        &env.owner.pubkey(),
+        &env.contest,
+        vec![env.curator.pubkey()],
+    );
+    env.bank
+        .send(&[set_curators_ix], &[&env.owner])
+        .await
+        .unwrap();
+    let approve_ix = approve_instruction(env, &env.curator.pubkey(), &env.artworks[0]);
+    env.bank.send(&[approve_ix], &[&env.curator]).await.unwrap();
+    let reject_ix = reject_instruction(env, 1);
+    env.bank.send(&[reject_ix], &[&env.owner]).await.unwrap();
+}
+
+/// Both voters vote for the first artwork, which the judge scores 8.
+pub async fn vote_and_score(env: &mut Env) {
+    env.bank.now = VOTE_AT;
+    for voter in 0..2 {
+        let vote_ix = vote_instruction(env, voter, 0);
+        env.bank
+            .send(&[vote_ix], &[&env.voters[voter]])
+            .await
+            .unwrap();
+    }
+    let score_ix = score_instruction(env, &env.judge.pubkey(), &env.artworks[0], 8);
+    env.bank.send(&[score_ix], &[&env.judge]).await.unwrap();
+}
+
+/// Judge scores are mixed with votes, so every artwork is tallied twice.
+pub async fn tally_and_finalize(env: &mut Env) {
+    env.bank.now = TALLY_AT;
+    for _ in 0..2 {
+        let tally_ix = instructions::tally_votes(&env.contest, &env.artworks);
+        env.bank.send(&[tally_ix], &[]).await.unwrap();
+    }
+    let finalize_ix = instructions::finalize_contest(&env.contest);
+    env.bank.send(&[finalize_ix], &[]).await.unwrap();
+}
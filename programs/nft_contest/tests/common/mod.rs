@@ -0,0 +1,489 @@
+//! Bank and genesis helpers shared by the integration tests. The program runs natively, with
+//! the token program as a native processor and CPI stubs papering over what the native CPI path
+//! of solana-program-test 1.9 does not support.
+#![allow(dead_code)]
+
+pub mod lifecycle;
+
+use anchor_lang::prelude::Clock;
+use anchor_lang::{
+    AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use mpl_token_metadata::state::{Creator, Data, Key, Metadata, MAX_METADATA_LEN};
+use nft_contest_client::pda;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::program_stubs::{self, SyscallStubs};
+use solana_sdk::program_utils::limited_deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+use solana_sdk::transport::TransportError;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+pub type TxResult = Result<(), TransportError>;
+
+// returned for a panicking handler, which aborts the transaction on chain
+pub const PROGRAM_PANICKED: u32 = u32::MAX;
+
+/// The bank under test. Every transaction is sent in a new slot, with the clock set to `now`.
+pub struct Bank {
+    pub context: ProgramTestContext,
+    pub slot: u64,
+    pub now: i64,
+}
+
+impl Bank {
+    pub async fn start(program_test: ProgramTest, now: i64) -> Bank {
+        let mut context = program_test.start_with_context().await;
+        install_cpi_stubs(&mut context).await;
+        Bank {
+            context,
+            slot: 1,
+            now,
+        }
+    }
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        self.slot += 2;
+        self.context.warp_to_slot(self.slot).unwrap();
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = self.now;
+        self.context.set_sysvar(&clock);
+
+        let blockhash = self
+            .context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn try_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> Option<T> {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()?;
+        Some(T::try_deserialize(&mut account.data.as_slice()).unwrap())
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .expect("token account not found");
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn exists(&mut self, address: &Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+}
+
+/// Anchor 0.24 leaves the callee program out of the account infos of its CPIs, which the runtime
+/// accepts but the native CPI stubs of solana-program-test 1.9 don't. These stubs add a copy of
+/// the program account before forwarding every call to the stubs of solana-program-test.
+struct CpiProgramAccountStubs {
+    inner: Box<dyn SyscallStubs>,
+    program_accounts: Vec<(Pubkey, Account)>,
+}
+
+impl CpiProgramAccountStubs {
+    /// The native CPI path refuses to copy a resized account back into the caller, which every
+    /// system program allocation does. The callee has already run by then, with the owner and
+    /// lamports copied back, so only the data is left to grow here.
+    fn invoke_resizing(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let (address, space) = match allocated_space(instruction) {
+            Some(allocation) => allocation,
+            None => {
+                return self
+                    .inner
+                    .sol_invoke_signed(instruction, account_infos, signers_seeds)
+            }
+        };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.inner
+                .sol_invoke_signed(instruction, account_infos, signers_seeds)
+        }));
+        match result {
+            Ok(result) => result,
+            Err(payload) if is_resizing_panic(&*payload) => {
+                let info = account_infos
+                    .iter()
+                    .find(|info| *info.key == address)
+                    .unwrap();
+                *info.data.borrow_mut() = Box::leak(vec![0; space].into_boxed_slice());
+                Ok(())
+            }
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+fn allocated_space(instruction: &Instruction) -> Option<(Pubkey, usize)> {
+    if instruction.program_id != system_program::ID {
+        return None;
+    }
+    let (index, space) = match limited_deserialize(&instruction.data).ok()? {
+        SystemInstruction::CreateAccount { space, .. }
+        | SystemInstruction::CreateAccountWithSeed { space, .. } => (1, space),
+        SystemInstruction::Allocate { space }
+        | SystemInstruction::AllocateWithSeed { space, .. } => (0, space),
+        _ => return None,
+    };
+    Some((instruction.accounts[index].pubkey, space as usize))
+}
+
+fn is_resizing_panic(payload: &(dyn Any + Send)) -> bool {
+    let message = payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or_default();
+    message.starts_with("Account data resizing not supported")
+}
+
+impl SyscallStubs for CpiProgramAccountStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let program_account = self
+            .program_accounts
+            .iter()
+            .find(|(address, _)| *address == instruction.program_id);
+        let (address, account) = match program_account {
+            Some(program_account)
+                if !account_infos
+                    .iter()
+                    .any(|info| *info.key == instruction.program_id) =>
+            {
+                program_account
+            }
+            _ => return self.invoke_resizing(instruction, account_infos, signers_seeds),
+        };
+        // leaked, so that the account info outlives the call
+        let program_info = AccountInfo::new(
+            Box::leak(Box::new(*address)),
+            false,
+            false,
+            Box::leak(Box::new(account.lamports)),
+            Box::leak(account.data.clone().into_boxed_slice()),
+            Box::leak(Box::new(account.owner)),
+            account.executable,
+            account.rent_epoch,
+        );
+        let mut infos = account_infos.to_vec();
+        infos.push(unsafe {
+            std::mem::transmute::<AccountInfo<'static>, AccountInfo>(program_info)
+        });
+        self.invoke_resizing(instruction, &infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+}
+
+struct PlaceholderStubs;
+
+impl SyscallStubs for PlaceholderStubs {}
+
+async fn install_cpi_stubs(context: &mut ProgramTestContext) {
+    static INSTALL: Once = Once::new();
+    let mut program_accounts = vec![];
+    for address in [system_program::ID, spl_token::ID] {
+        let account = context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        program_accounts.push((address, account));
+    }
+    INSTALL.call_once(|| {
+        let stubs = CpiProgramAccountStubs {
+            inner: program_stubs::set_syscall_stubs(Box::new(PlaceholderStubs)),
+            program_accounts,
+        };
+        program_stubs::set_syscall_stubs(Box::new(stubs));
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !is_resizing_panic(info.payload()) {
+                default_hook(info)
+            }
+        }));
+    });
+}
+
+/// `AccountInfo::realloc` writes the new length in front of the data and grows the slice in
+/// place, as the BPF loader lays accounts out. Copies the data of the accounts the program could
+/// resize into leaked buffers laid out the same way.
+fn make_reallocatable(program_id: &Pubkey, accounts: &[AccountInfo]) {
+    for info in accounts {
+        if !info.is_writable || info.owner != program_id {
+            continue;
+        }
+        let len = info.data_len();
+        let buffer = Box::leak(vec![0; 8 + len + MAX_PERMITTED_DATA_INCREASE].into_boxed_slice());
+        buffer[..8].copy_from_slice(&(len as u64).to_le_bytes());
+        buffer[8..8 + len].copy_from_slice(&info.data.borrow());
+        *info.data.borrow_mut() = &mut buffer[8..8 + len];
+    }
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    make_reallocatable(program_id, accounts);
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        nft_contest::entry(program_id, accounts, data)
+    }))
+    .unwrap_or(Err(ProgramError::Custom(PROGRAM_PANICKED)))
+}
+
+/// The program under test, failing transactions when a handler panics like BPF does instead of
+/// taking the bank down.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "nft_contest",
+        nft_contest::ID,
+        processor!(process_instruction),
+    );
+    // the bundled token program is BPF, which the native CPI path cannot hand accounts to
+    program_test.add_program(
+        "spl_token",
+        spl_token::ID,
+        processor!(spl_token::processor::Processor::process),
+    );
+    program_test
+}
+
+pub fn add_user(program_test: &mut ProgramTest) -> Keypair {
+    let user = Keypair::new();
+    program_test.add_account(
+        user.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            ..Account::default()
+        },
+    );
+    user
+}
+
+pub fn add_packed<T: Pack>(program_test: &mut ProgramTest, state: T) -> Pubkey {
+    let address = Pubkey::new_unique();
+    add_packed_at(program_test, address, state);
+    address
+}
+
+pub fn add_packed_at<T: Pack>(program_test: &mut ProgramTest, address: Pubkey, state: T) {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    program_test.add_account(
+        address,
+        Account {
+            lamports: Rent::default().minimum_balance(T::LEN),
+            data,
+            owner: spl_token::ID,
+            ..Account::default()
+        },
+    );
+}
+
+pub fn add_mint(
+    program_test: &mut ProgramTest,
+    mint_authority: Option<Pubkey>,
+    decimals: u8,
+    supply: u64,
+) -> Pubkey {
+    add_packed(
+        program_test,
+        spl_token::state::Mint {
+            mint_authority: mint_authority.into(),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        },
+    )
+}
+
+pub fn add_token_account(
+    program_test: &mut ProgramTest,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    add_packed(
+        program_test,
+        spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        },
+    )
+}
+
+/// Metaplex metadata with the artist as verified creator, as submit requires.
+pub fn add_nft_metadata(program_test: &mut ProgramTest, mint: &Pubkey, artist: &Pubkey) {
+    let metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: *artist,
+        mint: *mint,
+        data: Data {
+            name: "Artwork".to_string(),
+            symbol: "ART".to_string(),
+            uri: "https://arweave.net/artwork".to_string(),
+            seller_fee_basis_points: 0,
+            creators: Some(vec![Creator {
+                address: *artist,
+                verified: true,
+                share: 100,
+            }]),
+        },
+        ..Metadata::default()
+    };
+    let mut data = metadata.try_to_vec().unwrap();
+    data.resize(MAX_METADATA_LEN, 0);
+    program_test.add_account(
+        pda::find_metadata_address(mint).0,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: mpl_token_metadata::ID,
+            ..Account::default()
+        },
+    );
+}
+
+pub fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nft_contest::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// ProgramData of the program, as deployed by the upgradeable loader with `upgrade_authority`.
+pub fn add_program_data(program_test: &mut ProgramTest, upgrade_authority: &Pubkey) {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority),
+    };
+    let space = UpgradeableLoaderState::programdata_data_offset().unwrap();
+    let mut account = Account::new_data_with_space(
+        Rent::default().minimum_balance(space),
+        &state,
+        space,
+        &bpf_loader_upgradeable::ID,
+    )
+    .unwrap();
+    account.rent_epoch = 0;
+    program_test.add_account(pda::find_program_data_address().0, account);
+}
+
+/// Program account written in a legacy layout, behind the discriminator of its current type, in
+/// `space` bytes as the first release sized accounts from their Rust layout.
+pub fn add_legacy_account<T: Discriminator>(
+    program_test: &mut ProgramTest,
+    address: Pubkey,
+    legacy: &impl AnchorSerialize,
+    space: usize,
+) {
+    let mut data = T::discriminator().to_vec();
+    data.extend(legacy.try_to_vec().unwrap());
+    assert!(data.len() <= space);
+    data.resize(space, 0);
+    program_test.add_account(
+        address,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: nft_contest::ID,
+            ..Account::default()
+        },
+    );
+}
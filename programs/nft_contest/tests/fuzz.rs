@@ -0,0 +1,820 @@
+//! Random sequences of launches, cancellations, submissions, reviews, votes, unlocks, tallies and
+//! claims sent by random users at random times against an in-process bank. After every step, whether the
+//! transaction succeeded or not, the invariants of every contest are checked:
+//! - prize tokens and NFTs are never created or lost,
+//! - the vote tallies match the VoteData accounts,
+//! - finalized winners and payouts match the off-chain simulation of the same tallies,
+//! - a prize vault holds the whole prize until finalization, then at least what is still owed to
+//!   artists and voters, and is only swept after the claim deadline,
+//! - the contest status agrees with finalization, with cancellation and with the closing of the
+//!   prize vault.
+//!
+//! Every case starts its own bank, so the harness is ignored by a plain `cargo test`:
+//! `cargo test -p nft_contest --test fuzz -- --ignored` runs it, PROPTEST_CASES=<n> sets the number
+//! of cases.
+
+mod common;
+
+use common::*;
+use nft_contest::state::*;
+use nft_contest_client::instructions::{self, LaunchArgs};
+use nft_contest_client::pda;
+use nft_contest_client::simulate::{ArtworkTally, Simulation};
+use proptest::prelude::*;
+use solana_program_test::tokio;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const USERS: usize = 3;
+const T0: i64 = 1_700_000_000;
+const PRIZE_BALANCE: u64 = 1_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Warp(i64),
+    // to the next deadline of a contest, or up to 20 seconds after
+    WarpToNextDeadline {
+        contest: usize,
+        offset: i64,
+    },
+    Launch {
+        owner: usize,
+        prize_amount: u64,
+        percentage_to_artist: u8,
+        curation_enabled: bool,
+        voter_reward_mode: VoterRewardMode,
+        // submission, review, voting and claim durations
+        durations: [u64; 4],
+        artist_vesting_duration: u64,
+    },
+    Cancel {
+        contest: usize,
+        signer: usize,
+    },
+    Submit {
+        contest: usize,
+        artist: usize,
+    },
+    Review {
+        contest: usize,
+        artwork: usize,
+        reviewer: usize,
+        approve: bool,
+    },
+    Vote {
+        contest: usize,
+        artwork: usize,
+        voter: usize,
+    },
+    UnlockVote {
+        contest: usize,
+        voter: usize,
+    },
+    Tally {
+        contest: usize,
+    },
+    Finalize {
+        contest: usize,
+    },
+    ClaimByArtist {
+        contest: usize,
+        artwork: usize,
+        signer: usize,
+    },
+    ClaimByVoter {
+        contest: usize,
+        artwork: usize,
+        voter: usize,
+    },
+    ClaimByContestOwner {
+        contest: usize,
+        artwork: usize,
+        signer: usize,
+    },
+    Distribute {
+        contest: usize,
+        voters: Vec<usize>,
+    },
+    Sweep {
+        contest: usize,
+        signer: usize,
+    },
+}
+
+fn voter_reward_mode() -> impl Strategy<Value = VoterRewardMode> {
+    prop_oneof![
+        Just(VoterRewardMode::WinnersOnly),
+        Just(VoterRewardMode::AllVoters),
+        Just(VoterRewardMode::VoteWeighted),
+        Just(VoterRewardMode::None),
+    ]
+}
+
+fn launch() -> impl Strategy<Value = Op> {
+    (
+        0..USERS,
+        0..=PRIZE_BALANCE / 2 + 100,
+        prop_oneof![Just(100u8), 0..=100u8],
+        prop::bool::weighted(0.3),
+        voter_reward_mode(),
+        [20..80u64, 20..60u64, 20..80u64, 0..200u64],
+        prop_oneof![Just(0u64), 0..100u64],
+    )
+        .prop_map(
+            |(
+                owner,
+                prize_amount,
+                percentage_to_artist,
+                curation_enabled,
+                voter_reward_mode,
+                durations,
+                artist_vesting_duration,
+            )| Op::Launch {
+                owner,
+                prize_amount,
+                percentage_to_artist,
+                curation_enabled,
+                voter_reward_mode,
+                durations,
+                artist_vesting_duration,
+            },
+        )
+}
+
+// contest indexes count back from the latest contest and, like artwork indexes, are reduced
+// modulo the number of known ones when applied
+fn op() -> impl Strategy<Value = Op> {
+    // mostly the latest contest
+    let contest = || prop_oneof![3 => Just(0usize), 1 => 0..8usize];
+    let index = || 0..8usize;
+    let user = || 0..USERS;
+    prop_oneof![
+        2 => (0..30i64).prop_map(Op::Warp),
+        4 => (contest(), 0..20i64)
+            .prop_map(|(contest, offset)| Op::WarpToNextDeadline { contest, offset }),
+        1 => launch(),
+        2 => (contest(), user()).prop_map(|(contest, signer)| Op::Cancel { contest, signer }),
+        8 => (contest(), user()).prop_map(|(contest, artist)| Op::Submit { contest, artist }),
+        2 => (contest(), index(), user(), any::<bool>()).prop_map(
+            |(contest, artwork, reviewer, approve)| Op::Review {
+                contest,
+                artwork,
+                reviewer,
+                approve,
+            }
+        ),
+        8 => (contest(), index(), user()).prop_map(|(contest, artwork, voter)| Op::Vote {
+            contest,
+            artwork,
+            voter,
+        }),
+        2 => (contest(), user()).prop_map(|(contest, voter)| Op::UnlockVote { contest, voter }),
+        4 => contest().prop_map(|contest| Op::Tally { contest }),
+        4 => contest().prop_map(|contest| Op::Finalize { contest }),
+        4 => (contest(), index(), user()).prop_map(|(contest, artwork, signer)| {
+            Op::ClaimByArtist {
+                contest,
+                artwork,
+                signer,
+            }
+        }),
+        4 => (contest(), index(), user()).prop_map(|(contest, artwork, voter)| {
+            Op::ClaimByVoter {
+                contest,
+                artwork,
+                voter,
+            }
+        }),
+        2 => (contest(), index(), user()).prop_map(|(contest, artwork, signer)| {
+            Op::ClaimByContestOwner {
+                contest,
+                artwork,
+                signer,
+            }
+        }),
+        2 => (contest(), prop::collection::vec(user(), 0..4))
+            .prop_map(|(contest, voters)| Op::Distribute { contest, voters }),
+        2 => (contest(), user()).prop_map(|(contest, signer)| Op::Sweep { contest, signer }),
+    ]
+}
+
+struct ArtworkEntry {
+    address: Pubkey,
+    artist: usize,
+    entry_index: u8,
+}
+
+struct ContestEntry {
+    address: Pubkey,
+    artworks: Vec<ArtworkEntry>,
+}
+
+/// Users each hold prize tokens and one NFT they created, plus an empty account for the NFTs of
+/// the other users, which they may claim as contest owners.
+struct World {
+    bank: Bank,
+    users: Vec<Keypair>,
+    prize_mint: Pubkey,
+    prize_accounts: Vec<Pubkey>,
+    nft_mints: Vec<Pubkey>,
+    // indexed by holder, then by artist
+    nft_accounts: Vec<Vec<Pubkey>>,
+    contests: Vec<ContestEntry>,
+}
+
+async fn setup() -> World {
+    let mut program_test = program_test();
+    let users: Vec<Keypair> = (0..USERS).map(|_| add_user(&mut program_test)).collect();
+    let prize_mint = add_mint(&mut program_test, None, 6, USERS as u64 * PRIZE_BALANCE);
+    let prize_accounts = users
+        .iter()
+        .map(|user| {
+            add_token_account(
+                &mut program_test,
+                &prize_mint,
+                &user.pubkey(),
+                PRIZE_BALANCE,
+            )
+        })
+        .collect();
+    let nft_mints: Vec<Pubkey> = users
+        .iter()
+        .map(|user| {
+            let mint = add_mint(&mut program_test, None, 0, 1);
+            add_nft_metadata(&mut program_test, &mint, &user.pubkey());
+            mint
+        })
+        .collect();
+    let nft_accounts = (0..USERS)
+        .map(|holder| {
+            (0..USERS)
+                .map(|artist| {
+                    let amount = (holder == artist) as u64;
+                    add_token_account(
+                        &mut program_test,
+                        &nft_mints[artist],
+                        &users[holder].pubkey(),
+                        amount,
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut bank = Bank::start(program_test, T0).await;
+    let program_owner = bank.context.payer.pubkey();
+    bank.send(&[instructions::initialize(&program_owner)], &[])
+        .await
+        .unwrap();
+    World {
+        bank,
+        users,
+        prize_mint,
+        prize_accounts,
+        nft_mints,
+        nft_accounts,
+        contests: vec![],
+    }
+}
+
+impl World {
+    fn contest(&self, index: usize) -> Option<&ContestEntry> {
+        match self.contests.len() {
+            0 => None,
+            len => self.contests.get(len - 1 - index % len),
+        }
+    }
+
+    fn artwork(&self, contest: usize, index: usize) -> Option<(&ContestEntry, &ArtworkEntry)> {
+        let contest = self.contest(contest)?;
+        match contest.artworks.len() {
+            0 => None,
+            len => Some((contest, &contest.artworks[index % len])),
+        }
+    }
+
+    async fn send(&mut self, ix: Instruction, signer: Option<usize>) -> bool {
+        let signers: Vec<&Keypair> = signer.iter().map(|&user| &self.users[user]).collect();
+        self.bank.send(&[ix], &signers).await.is_ok()
+    }
+
+    async fn apply(&mut self, op: &Op) {
+        match op {
+            Op::Warp(seconds) => self.bank.now += seconds,
+            Op::WarpToNextDeadline { contest, offset } => {
+                let contest: Contest = match self.contest(*contest) {
+                    Some(contest) => self.bank.account(&contest.address.clone()).await,
+                    None => return,
+                };
+                let deadlines = [
+                    contest.submit_end_at,
+                    contest.vote_start_at,
+                    contest.vote_end_at,
+                    contest.vote_end_at + contest.artist_vesting_duration,
+                    contest.claim_end_at,
+                ];
+                let now = self.bank.now as u64;
+                if let Some(deadline) = deadlines.iter().find(|&&deadline| deadline > now) {
+                    self.bank.now = *deadline as i64 + offset;
+                }
+            }
+            Op::Launch {
+                owner,
+                prize_amount,
+                percentage_to_artist,
+                curation_enabled,
+                voter_reward_mode,
+                durations,
+                artist_vesting_duration,
+            } => {
+                let owner_key = self.users[*owner].pubkey();
+                let counter: Counter = self.bank.account(&pda::find_counter_address().0).await;
+                let owner_contest_count = self
+                    .bank
+                    .try_account::<OwnerProfile>(&pda::find_owner_profile_address(&owner_key).0)
+                    .await
+                    .map_or(0, |profile| profile.contest_count);
+                let submit_start_at = self.bank.now as u64;
+                let submit_end_at = submit_start_at + durations[0];
+                let vote_start_at = submit_end_at + durations[1];
+                let vote_end_at = vote_start_at + durations[2];
+                let args = LaunchArgs {
+                    prize_amount: *prize_amount,
+                    percentage_to_artist: *percentage_to_artist,
+                    submit_start_at,
+                    submit_end_at,
+                    vote_start_at,
+                    vote_end_at,
+                    metadata: ContestMetadata {
+                        title_of_contest: "Fuzzed Contest".to_string(),
+                        ..ContestMetadata::default()
+                    },
+                    max_artworks: 4,
+                    required_collection: None,
+                    max_entries_per_artist: 1,
+                    curation_enabled: *curation_enabled,
+                    judges: vec![],
+                    judge_weight_percent: 0,
+                    artist_vesting_duration: *artist_vesting_duration,
+                    voter_reward_mode: *voter_reward_mode,
+                    claim_end_at: vote_end_at + artist_vesting_duration + durations[3] + 1,
+                };
+                let ix = instructions::launch(
+                    &owner_key,
+                    counter.contest_count,
+                    owner_contest_count,
+                    &self.prize_mint,
+                    &self.prize_accounts[*owner],
+                    args,
+                );
+                if self.send(ix, Some(*owner)).await {
+                    self.contests.push(ContestEntry {
+                        address: pda::find_contest_address(&owner_key, counter.contest_count).0,
+                        artworks: vec![],
+                    });
+                }
+            }
+            Op::Cancel { contest, signer } => {
+                let ix = match self.contest(*contest) {
+                    Some(contest) => instructions::cancel_contest(
+                        &self.users[*signer].pubkey(),
+                        &contest.address,
+                        &self.prize_mint,
+                        &self.prize_accounts[*signer],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*signer)).await;
+            }
+            Op::Submit { contest, artist } => {
+                let contest = match self.contest(*contest) {
+                    Some(contest) => contest.address,
+                    None => return,
+                };
+                let artist_key = self.users[*artist].pubkey();
+                let entry_index = self
+                    .bank
+                    .try_account::<ArtistEntries>(
+                        &pda::find_artist_entries_address(&contest, &artist_key).0,
+                    )
+                    .await
+                    .map_or(0, |entries| entries.entry_count);
+                let ix = instructions::submit(
+                    &artist_key,
+                    &contest,
+                    entry_index,
+                    &self.nft_mints[*artist],
+                    &self.nft_accounts[*artist][*artist],
+                );
+                if self.send(ix, Some(*artist)).await {
+                    let entry = self
+                        .contests
+                        .iter_mut()
+                        .find(|entry| entry.address == contest)
+                        .unwrap();
+                    entry.artworks.push(ArtworkEntry {
+                        address: pda::find_artwork_address(&contest, &artist_key, entry_index).0,
+                        artist: *artist,
+                        entry_index,
+                    });
+                }
+            }
+            Op::Review {
+                contest,
+                artwork,
+                reviewer,
+                approve,
+            } => {
+                let (contest, artwork) = match self.artwork(*contest, *artwork) {
+                    Some(found) => found,
+                    None => return,
+                };
+                let reviewer_key = self.users[*reviewer].pubkey();
+                let ix = if *approve {
+                    ix(
+                        nft_contest::accounts::ApproveArtwork {
+                            reviewer: reviewer_key,
+                            contest: contest.address,
+                            artwork: artwork.address,
+                        },
+                        nft_contest::instruction::ApproveArtwork {},
+                    )
+                } else {
+                    let artist_key = self.users[artwork.artist].pubkey();
+                    ix(
+                        nft_contest::accounts::RejectArtwork {
+                            reviewer: reviewer_key,
+                            contest: contest.address,
+                            artwork: artwork.address,
+                            nft_mint: self.nft_mints[artwork.artist],
+                            nft_vault_account: pda::find_nft_vault_address(
+                                &contest.address,
+                                &artist_key,
+                                artwork.entry_index,
+                            )
+                            .0,
+                            nft_vault_authority: pda::find_nft_vault_authority_address(
+                                &contest.address,
+                                &artist_key,
+                            )
+                            .0,
+                            artist_nft_token_account: self.nft_accounts[artwork.artist]
+                                [artwork.artist],
+                            token_program: spl_token::ID,
+                        },
+                        nft_contest::instruction::RejectArtwork {},
+                    )
+                };
+                self.send(ix, Some(*reviewer)).await;
+            }
+            Op::Vote {
+                contest,
+                artwork,
+                voter,
+            } => {
+                let (contest, artwork_id, artwork) = match self.artwork(*contest, *artwork) {
+                    Some((contest, artwork)) => (
+                        contest.address,
+                        contest
+                            .artworks
+                            .iter()
+                            .position(|entry| entry.address == artwork.address)
+                            .unwrap(),
+                        artwork.address,
+                    ),
+                    None => return,
+                };
+                let ix = instructions::vote(
+                    &self.users[*voter].pubkey(),
+                    &contest,
+                    &artwork,
+                    artwork_id as u64,
+                    &self.prize_mint,
+                    &self.prize_accounts[*voter],
+                );
+                self.send(ix, Some(*voter)).await;
+            }
+            Op::UnlockVote { contest, voter } => {
+                let ix = match self.contest(*contest) {
+                    Some(contest) => instructions::unlock_vote(
+                        &self.users[*voter].pubkey(),
+                        &contest.address,
+                        &self.prize_accounts[*voter],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*voter)).await;
+            }
+            Op::Tally { contest } => {
+                let (contest, artworks) = match self.contest(*contest) {
+                    Some(contest) => (
+                        contest.address,
+                        contest
+                            .artworks
+                            .iter()
+                            .map(|artwork| artwork.address)
+                            .collect::<Vec<_>>(),
+                    ),
+                    None => return,
+                };
+                let state: Contest = self.bank.account(&contest).await;
+                let remaining =
+                    &artworks[(state.tallied_artwork_count as usize).min(artworks.len())..];
+                self.send(instructions::tally_votes(&contest, remaining), None)
+                    .await;
+            }
+            Op::Finalize { contest } => {
+                if let Some(contest) = self.contest(*contest) {
+                    let ix = instructions::finalize_contest(&contest.address);
+                    self.send(ix, None).await;
+                }
+            }
+            Op::ClaimByArtist {
+                contest,
+                artwork,
+                signer,
+            } => {
+                let ix = match self.artwork(*contest, *artwork) {
+                    Some((contest, artwork)) => instructions::claim_by_artist(
+                        &self.users[*signer].pubkey(),
+                        &contest.address,
+                        artwork.entry_index,
+                        &self.prize_mint,
+                        &self.prize_accounts[*signer],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*signer)).await;
+            }
+            Op::ClaimByVoter {
+                contest,
+                artwork,
+                voter,
+            } => {
+                let ix = match self.artwork(*contest, *artwork) {
+                    Some((contest, artwork)) => instructions::claim_by_voter(
+                        &self.users[*voter].pubkey(),
+                        &contest.address,
+                        &artwork.address,
+                        &self.prize_mint,
+                        &self.prize_accounts[*voter],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*voter)).await;
+            }
+            Op::ClaimByContestOwner {
+                contest,
+                artwork,
+                signer,
+            } => {
+                let ix = match self.artwork(*contest, *artwork) {
+                    Some((contest, artwork)) => instructions::claim_by_contest_owner(
+                        &self.users[*signer].pubkey(),
+                        &contest.address,
+                        &self.users[artwork.artist].pubkey(),
+                        artwork.entry_index,
+                        &self.nft_mints[artwork.artist],
+                        &self.nft_accounts[*signer][artwork.artist],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*signer)).await;
+            }
+            Op::Distribute { contest, voters } => {
+                let ix = match self.contest(*contest) {
+                    Some(contest) => {
+                        let voters: Vec<(Pubkey, Pubkey)> = voters
+                            .iter()
+                            .map(|&voter| (self.users[voter].pubkey(), self.prize_accounts[voter]))
+                            .collect();
+                        instructions::distribute(&contest.address, &self.prize_mint, &voters)
+                    }
+                    None => return,
+                };
+                self.send(ix, None).await;
+            }
+            Op::Sweep { contest, signer } => {
+                let ix = match self.contest(*contest) {
+                    Some(contest) => instructions::sweep_remainder(
+                        &self.users[*signer].pubkey(),
+                        &contest.address,
+                        &self.prize_mint,
+                        &self.prize_accounts[*signer],
+                    ),
+                    None => return,
+                };
+                self.send(ix, Some(*signer)).await;
+            }
+        }
+    }
+
+    async fn token_balance_if_exists(&mut self, address: &Pubkey) -> u64 {
+        if self.bank.exists(address).await {
+            self.bank.token_balance(address).await
+        } else {
+            0
+        }
+    }
+
+    async fn check_invariants(&mut self) {
+        let now = self.bank.now as u64;
+
+        // prize tokens only move between users, vaults and vote escrows
+        let mut prize_total = 0;
+        for user in 0..USERS {
+            prize_total += self.bank.token_balance(&self.prize_accounts[user]).await;
+        }
+        for index in 0..self.contests.len() {
+            let contest = self.contests[index].address;
+            let vault = pda::find_prize_vault_address(&contest).0;
+            prize_total += self.token_balance_if_exists(&vault).await;
+            for user in 0..USERS {
+                let escrow = pda::find_vote_escrow_address(&contest, &self.users[user].pubkey()).0;
+                prize_total += self.token_balance_if_exists(&escrow).await;
+            }
+        }
+        assert_eq!(
+            prize_total,
+            USERS as u64 * PRIZE_BALANCE,
+            "prize tokens lost"
+        );
+
+        // each NFT is held by a user or sits in the vault of one of its artworks
+        for artist in 0..USERS {
+            let mut held = 0;
+            for holder in 0..USERS {
+                held += self
+                    .bank
+                    .token_balance(&self.nft_accounts[holder][artist])
+                    .await;
+            }
+            let vaults: Vec<Pubkey> = self
+                .contests
+                .iter()
+                .flat_map(|contest| {
+                    contest
+                        .artworks
+                        .iter()
+                        .filter(|artwork| artwork.artist == artist)
+                        .map(|artwork| {
+                            pda::find_nft_vault_address(
+                                &contest.address,
+                                &self.users[artist].pubkey(),
+                                artwork.entry_index,
+                            )
+                            .0
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            for vault in vaults {
+                held += self.token_balance_if_exists(&vault).await;
+            }
+            assert_eq!(held, 1, "NFT of user {} lost or duplicated", artist);
+        }
+
+        for index in 0..self.contests.len() {
+            let address = self.contests[index].address;
+            let contest: Contest = self.bank.account(&address).await;
+            let mut artworks = vec![];
+            for artwork in &self.contests[index].artworks {
+                artworks.push(self.bank.account::<Artwork>(&artwork.address).await);
+            }
+            let mut votes = vec![];
+            for user in &self.users {
+                let vote_data = pda::find_vote_data_address(&address, &user.pubkey()).0;
+                if let Some(vote) = self.bank.try_account::<VoteData>(&vote_data).await {
+                    votes.push(vote);
+                }
+            }
+
+            // every vote is counted once, on the artwork it was cast for
+            for (artwork_id, artwork) in artworks.iter().enumerate() {
+                let cast: Vec<&VoteData> = votes
+                    .iter()
+                    .filter(|vote| vote.voted_artwork_id == artwork_id as u64)
+                    .collect();
+                assert_eq!(artwork.vote_count, cast.len() as u64);
+                assert_eq!(
+                    artwork.vote_weight_total,
+                    cast.iter().map(|vote| vote.weight).sum::<u64>()
+                );
+            }
+            assert!(votes
+                .iter()
+                .all(|vote| (vote.voted_artwork_id as usize) < artworks.len()));
+            if contest.is_tallied() {
+                assert_eq!(contest.total_vote_count, votes.len() as u64);
+            }
+
+            // the off-chain simulation of the same tallies records the same result
+            if contest.is_finalized {
+                let tallies: Vec<ArtworkTally> = artworks.iter().map(ArtworkTally::from).collect();
+                let simulation = Simulation::run(&contest, &tallies).unwrap();
+                let simulated = &simulation.contest;
+                assert_eq!(simulated.winning_artwork_ids, contest.winning_artwork_ids);
+                assert_eq!(
+                    simulated.artist_payout_per_winner,
+                    contest.artist_payout_per_winner
+                );
+                assert_eq!(simulated.voter_pool_amount, contest.voter_pool_amount);
+                assert_eq!(
+                    simulated.voter_payout_per_vote,
+                    contest.voter_payout_per_vote
+                );
+                for vote in &votes {
+                    assert_eq!(
+                        simulation
+                            .voter_reward(vote.voted_artwork_id, vote.weight)
+                            .unwrap(),
+                        contest.voter_reward(vote).unwrap()
+                    );
+                }
+            }
+
+            // the status follows finalization, and the sweep settles the contest
+            let status = contest.current_status(now);
+            assert_eq!(
+                contest.is_finalized,
+                matches!(status, ContestStatus::Finalized | ContestStatus::Settled),
+                "status {:?} of a contest finalized: {}",
+                status,
+                contest.is_finalized
+            );
+
+            let vault = pda::find_prize_vault_address(&address).0;
+            if status == ContestStatus::Cancelled {
+                assert!(
+                    !self.bank.exists(&vault).await,
+                    "cancelled contest kept its vault"
+                );
+                assert!(artworks.is_empty(), "cancelled contest had artworks");
+                continue;
+            }
+            if !self.bank.exists(&vault).await {
+                assert!(
+                    contest.claim_end_at < now,
+                    "vault swept before the deadline"
+                );
+                assert_eq!(status, ContestStatus::Settled);
+                continue;
+            }
+            let balance = self.bank.token_balance(&vault).await;
+            if !contest.is_finalized {
+                assert_eq!(
+                    balance, contest.prize_amount,
+                    "prize paid before finalization"
+                );
+                continue;
+            }
+            let mut owed = 0;
+            for &artwork_id in &contest.winning_artwork_ids {
+                owed += contest.artist_payout_per_winner
+                    - artworks[artwork_id as usize].prize_released_amount;
+            }
+            for vote in votes.iter().filter(|vote| !vote.reward_claimed) {
+                owed += contest.voter_reward(vote).unwrap();
+            }
+            assert!(balance >= owed, "vault holds {} but owes {}", balance, owed);
+        }
+    }
+}
+
+async fn run(ops: Vec<Op>) {
+    let mut world = setup().await;
+    for op in &ops {
+        world.apply(op).await;
+        world.check_invariants().await;
+    }
+}
+
+fn config() -> ProptestConfig {
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(12);
+    ProptestConfig {
+        cases,
+        max_shrink_iters: 64,
+        ..ProptestConfig::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    #[ignore = "takes tens of seconds, run with --ignored"]
+    fn random_instruction_sequences_keep_invariants(
+        first_launch in launch(),
+        ops in prop::collection::vec(op(), 1..100),
+    ) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run([vec![first_launch], ops].concat()));
+    }
+}
@@ -0,0 +1,998 @@
+//! Offline integration tests running the program natively in solana-program-test, with the clock
+//! moved through the phases of a contest. NFTs and their Metaplex metadata are written at genesis.
+
+mod common;
+
+use common::lifecycle::*;
+use common::*;
+use nft_contest::common::lib::ErrorCode;
+use nft_contest::state::*;
+use nft_contest_client::instructions::{self, LaunchArgs};
+use nft_contest_client::pda;
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction;
+
+#[tokio::test]
+async fn full_contest_lifecycle() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    let counter: Counter = env.bank.account(&pda::find_counter_address().0).await;
+    assert_eq!(counter.contest_count, 1);
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.contest_owner, env.owner.pubkey());
+    assert_eq!(contest.prize_mint, env.prize_mint);
+    // launched at submit_start_at
+    assert_eq!(contest.status, ContestStatus::Open);
+    let prize_vault = pda::find_prize_vault_address(&env.contest).0;
+    assert_eq!(env.bank.token_balance(&prize_vault).await, PRIZE_AMOUNT);
+
+    // the owner edits the contest before voting starts
+    let update_ix =
+        update_metadata_instruction(&env.contest, &env.owner.pubkey(), "Renamed Contest");
+    let increase_ix = ix(
+        nft_contest::accounts::IncreaseMaxArtworks {
+            contest_owner: env.owner.pubkey(),
+            contest: env.contest,
+        },
+        nft_contest::instruction::IncreaseMaxArtworks {
+            new_max_artworks: 10,
+        },
+    );
+    env.bank
+        .send(&[update_ix, increase_ix], &[&env.owner])
+        .await
+        .unwrap();
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.title_of_contest, "Renamed Contest");
+    assert_eq!(contest.max_artworks, 10);
+
+    submit_entries(&mut env).await;
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.artwork_count, 2);
+    let artwork: Artwork = env.bank.account(&env.artworks[1]).await;
+    assert_eq!(artwork.artwork_id, 1);
+    assert_eq!(artwork.status, ArtworkStatus::Pending);
+    assert_eq!(env.bank.token_balance(&env.artist_nft_accounts[1]).await, 0);
+
+    review_entries(&mut env).await;
+    let artwork: Artwork = env.bank.account(&env.artworks[0]).await;
+    assert_eq!(artwork.status, ArtworkStatus::Approved);
+    let artwork: Artwork = env.bank.account(&env.artworks[1]).await;
+    assert_eq!(artwork.status, ArtworkStatus::Rejected);
+    // a rejected NFT goes back to the artist
+    assert_eq!(env.bank.token_balance(&env.artist_nft_accounts[1]).await, 1);
+
+    vote_and_score(&mut env).await;
+    let artwork: Artwork = env.bank.account(&env.artworks[0]).await;
+    assert_eq!(artwork.vote_count, 2);
+    assert_eq!(artwork.judge_score_total, 8);
+
+    // votes don't write the contest, the tally stores that voting has started
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.status, ContestStatus::Open);
+    assert_eq!(
+        contest.current_status(VOTE_AT as u64),
+        ContestStatus::Voting
+    );
+
+    tally_and_finalize(&mut env).await;
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert!(contest.is_finalized);
+    assert_eq!(contest.status, ContestStatus::Finalized);
+    assert_eq!(contest.total_vote_count, 2);
+    assert_eq!(contest.winning_artwork_ids, vec![0]);
+    assert_eq!(contest.artist_payout_per_winner, 700_000);
+    assert_eq!(contest.voter_payout_per_vote, 150_000);
+
+    let claim_ix = claim_by_artist_instruction(&env, 0);
+    env.bank.send(&[claim_ix], &[&env.artist]).await.unwrap();
+    assert_eq!(
+        env.bank.token_balance(&env.artist_prize_account).await,
+        700_000
+    );
+
+    let claim_ix = claim_by_voter_instruction(&env, 0);
+    env.bank.send(&[claim_ix], &[&env.voters[0]]).await.unwrap();
+    assert_eq!(
+        env.bank.token_balance(&env.voter_prize_accounts[0]).await,
+        300 + 150_000
+    );
+
+    // the second voter is paid by the crank
+    let distribute_ix = instructions::distribute(
+        &env.contest,
+        &env.prize_mint,
+        &[(env.voters[1].pubkey(), env.voter_prize_accounts[1])],
+    );
+    env.bank.send(&[distribute_ix], &[]).await.unwrap();
+    assert_eq!(
+        env.bank.token_balance(&env.voter_prize_accounts[1]).await,
+        100 + 150_000
+    );
+
+    let claim_ix = instructions::claim_by_contest_owner(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.artist.pubkey(),
+        0,
+        &env.nft_mints[0],
+        &env.owner_nft_account,
+    );
+    env.bank.send(&[claim_ix], &[&env.owner]).await.unwrap();
+    assert_eq!(env.bank.token_balance(&env.owner_nft_account).await, 1);
+
+    // every share was claimed, so the sweep only closes the vault
+    env.bank.now = CLAIM_END_AT as i64 + 1;
+    let sweep_ix = sweep_instruction(&env);
+    env.bank.send(&[sweep_ix], &[&env.owner]).await.unwrap();
+    assert!(!env.bank.exists(&prize_vault).await);
+    assert_eq!(
+        env.bank.token_balance(&env.owner_prize_account).await,
+        PRIZE_AMOUNT
+    );
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.status, ContestStatus::Settled);
+}
+
+#[tokio::test]
+async fn rejects_operations_in_the_wrong_phase() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+
+    // submission period
+    let cancel_ix = instructions::cancel_contest(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.prize_mint,
+        &env.owner_prize_account,
+    );
+    assert_error(
+        env.bank.send(&[cancel_ix], &[&env.owner]).await,
+        ErrorCode::ContestHasArtworks,
+    );
+    let vote_ix = vote_instruction(&env, 0, 0);
+    assert_error(
+        env.bank.send(&[vote_ix], &[&env.voters[0]]).await,
+        ErrorCode::NotInVotingPeriod,
+    );
+    let approve_ix = approve_instruction(&env, &env.owner.pubkey(), &env.artworks[0]);
+    assert_error(
+        env.bank.send(&[approve_ix], &[&env.owner]).await,
+        ErrorCode::NotInReviewPeriod,
+    );
+
+    // review period: artworks are not votable until approved
+    review_entries(&mut env).await;
+
+    // voting period
+    env.bank.now = VOTE_AT;
+    let update_ix = update_metadata_instruction(&env.contest, &env.owner.pubkey(), "Too Late");
+    assert_error(
+        env.bank.send(&[update_ix], &[&env.owner]).await,
+        ErrorCode::MetadataLocked,
+    );
+    let vote_ix = vote_instruction(&env, 0, 1);
+    assert_error(
+        env.bank.send(&[vote_ix], &[&env.voters[0]]).await,
+        ErrorCode::ArtworkNotApproved,
+    );
+    let finalize_ix = instructions::finalize_contest(&env.contest);
+    assert_error(
+        env.bank.send(&[finalize_ix], &[]).await,
+        ErrorCode::VotingNotEnded,
+    );
+    vote_and_score(&mut env).await;
+
+    // after voting, before finalization
+    env.bank.now = TALLY_AT;
+    let finalize_ix = instructions::finalize_contest(&env.contest);
+    assert_error(
+        env.bank.send(&[finalize_ix], &[]).await,
+        ErrorCode::VotesNotTallied,
+    );
+    let claim_ix = claim_by_artist_instruction(&env, 0);
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.artist]).await,
+        ErrorCode::ContestNotFinalized,
+    );
+    let claim_ix = instructions::claim_by_contest_owner(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.artist.pubkey(),
+        0,
+        &env.nft_mints[0],
+        &env.owner_nft_account,
+    );
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.owner]).await,
+        ErrorCode::InvalidContestStatus,
+    );
+
+    tally_and_finalize(&mut env).await;
+    let sweep_ix = sweep_instruction(&env);
+    assert_error(
+        env.bank.send(&[sweep_ix], &[&env.owner]).await,
+        ErrorCode::ClaimPeriodNotEnded,
+    );
+
+    // after the claim deadline
+    env.bank.now = CLAIM_END_AT as i64 + 1;
+    let claim_ix = claim_by_voter_instruction(&env, 0);
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.voters[0]]).await,
+        ErrorCode::ClaimPeriodEnded,
+    );
+}
+
+#[tokio::test]
+async fn rejects_invalid_launches_and_late_submissions() {
+    let mut env = setup().await;
+    let program_owner = env.bank.context.payer.pubkey();
+    env.bank
+        .send(&[instructions::initialize(&program_owner)], &[])
+        .await
+        .unwrap();
+
+    let launch_with = |args: LaunchArgs| {
+        instructions::launch(
+            &env.owner.pubkey(),
+            0,
+            0,
+            &env.prize_mint,
+            &env.owner_prize_account,
+            args,
+        )
+    };
+    let launch_ix = launch_with(LaunchArgs {
+        vote_end_at: VOTE_START_AT - 1,
+        ..launch_args(&env)
+    });
+    assert_error(
+        env.bank.send(&[launch_ix], &[&env.owner]).await,
+        ErrorCode::InvalidSchedule,
+    );
+    let launch_ix = launch_with(LaunchArgs {
+        percentage_to_artist: 101,
+        ..launch_args(&env)
+    });
+    assert_error(
+        env.bank.send(&[launch_ix], &[&env.owner]).await,
+        ErrorCode::InvalidPercentage,
+    );
+    let launch_ix = launch_with(LaunchArgs {
+        prize_amount: 3 * PRIZE_AMOUNT,
+        ..launch_args(&env)
+    });
+    assert_error(
+        env.bank.send(&[launch_ix], &[&env.owner]).await,
+        ErrorCode::InsufficientPrize,
+    );
+    let launch_ix = launch_with(launch_args(&env));
+    env.bank.send(&[launch_ix], &[&env.owner]).await.unwrap();
+
+    env.bank.now = REVIEW_AT;
+    let submit_ix = instructions::submit(
+        &env.artist.pubkey(),
+        &env.contest,
+        0,
+        &env.nft_mints[0],
+        &env.artist_nft_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[submit_ix], &[&env.artist]).await,
+        ErrorCode::SubmissionClosed,
+    );
+}
+
+#[tokio::test]
+async fn cancels_contest_before_submissions() {
+    let mut env = setup().await;
+    let program_owner = env.bank.context.payer.pubkey();
+    env.bank
+        .send(&[instructions::initialize(&program_owner)], &[])
+        .await
+        .unwrap();
+    let args = LaunchArgs {
+        submit_start_at: SUBMIT_AT as u64,
+        ..launch_args(&env)
+    };
+    let launch_ix = instructions::launch(
+        &env.owner.pubkey(),
+        0,
+        0,
+        &env.prize_mint,
+        &env.owner_prize_account,
+        args,
+    );
+    env.bank.send(&[launch_ix], &[&env.owner]).await.unwrap();
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.status, ContestStatus::Draft);
+
+    // submissions are not open yet
+    let submit_ix = instructions::submit(
+        &env.artist.pubkey(),
+        &env.contest,
+        0,
+        &env.nft_mints[0],
+        &env.artist_nft_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[submit_ix], &[&env.artist]).await,
+        ErrorCode::InvalidContestStatus,
+    );
+
+    let cancel_ix = instructions::cancel_contest(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.prize_mint,
+        &env.owner_prize_account,
+    );
+    env.bank.send(&[cancel_ix], &[&env.owner]).await.unwrap();
+    let contest: Contest = env.bank.account(&env.contest).await;
+    assert_eq!(contest.status, ContestStatus::Cancelled);
+    assert!(
+        !env.bank
+            .exists(&pda::find_prize_vault_address(&env.contest).0)
+            .await
+    );
+    assert_eq!(
+        env.bank.token_balance(&env.owner_prize_account).await,
+        2 * PRIZE_AMOUNT
+    );
+
+    // the schedule no longer opens a cancelled contest
+    env.bank.now = SUBMIT_AT;
+    let submit_ix = instructions::submit(
+        &env.artist.pubkey(),
+        &env.contest,
+        0,
+        &env.nft_mints[0],
+        &env.artist_nft_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[submit_ix], &[&env.artist]).await,
+        ErrorCode::InvalidContestStatus,
+    );
+}
+
+#[tokio::test]
+async fn returns_rejected_and_unreviewed_nfts() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+
+    // a rejected NFT goes back to the artist right away
+    env.bank.now = REVIEW_AT;
+    let reject_ix = reject_instruction(&env, 1);
+    env.bank.send(&[reject_ix], &[&env.owner]).await.unwrap();
+    assert_eq!(env.bank.token_balance(&env.artist_nft_accounts[1]).await, 1);
+    let artwork: Artwork = env.bank.account(&env.artworks[1]).await;
+    assert_eq!(artwork.status, ArtworkStatus::Rejected);
+
+    // a pending entry may still be reviewed until voting starts
+    let withdraw_ix = withdraw_instruction(&env, 0);
+    assert_error(
+        env.bank.send(&[withdraw_ix], &[&env.artist]).await,
+        ErrorCode::ReviewNotEnded,
+    );
+
+    // then its artist can take the NFT back
+    env.bank.now = VOTE_AT;
+    let vote_ix = vote_instruction(&env, 0, 0);
+    assert_error(
+        env.bank.send(&[vote_ix], &[&env.voters[0]]).await,
+        ErrorCode::ArtworkNotApproved,
+    );
+    let withdraw_ix = withdraw_instruction(&env, 0);
+    env.bank.send(&[withdraw_ix], &[&env.artist]).await.unwrap();
+    assert_eq!(env.bank.token_balance(&env.artist_nft_accounts[0]).await, 1);
+    let artwork: Artwork = env.bank.account(&env.artworks[0]).await;
+    assert_eq!(artwork.status, ArtworkStatus::Rejected);
+
+    let withdraw_ix = withdraw_instruction(&env, 1);
+    assert_error(
+        env.bank.send(&[withdraw_ix], &[&env.artist]).await,
+        ErrorCode::ArtworkAlreadyReviewed,
+    );
+}
+
+#[tokio::test]
+async fn keeps_unreviewed_nfts_from_the_contest_owner() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+    env.bank.now = REVIEW_AT;
+    let reject_ix = reject_instruction(&env, 1);
+    env.bank.send(&[reject_ix], &[&env.owner]).await.unwrap();
+    tally_and_finalize(&mut env).await;
+
+    // the first entry was never reviewed, it stays claimable by its artist only
+    let claim_ix = instructions::claim_by_contest_owner(
+        &env.owner.pubkey(),
+        &env.contest,
+        &env.artist.pubkey(),
+        0,
+        &env.nft_mints[0],
+        &env.owner_nft_account,
+    );
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.owner]).await,
+        ErrorCode::ArtworkNotApproved,
+    );
+    let withdraw_ix = withdraw_instruction(&env, 0);
+    env.bank.send(&[withdraw_ix], &[&env.artist]).await.unwrap();
+    assert_eq!(env.bank.token_balance(&env.artist_nft_accounts[0]).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_wrong_signers() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+
+    let update_ix = update_metadata_instruction(&env.contest, &env.artist.pubkey(), "Hijacked");
+    assert_error(
+        env.bank.send(&[update_ix], &[&env.artist]).await,
+        anchor_lang::error::ErrorCode::ConstraintHasOne,
+    );
+
+    submit_entries(&mut env).await;
+    env.bank.now = REVIEW_AT;
+    let approve_ix = approve_instruction(&env, &env.voters[0].pubkey(), &env.artworks[0]);
+    assert_error(
+        env.bank.send(&[approve_ix], &[&env.voters[0]]).await,
+        ErrorCode::NotReviewer,
+    );
+    review_entries(&mut env).await;
+
+    env.bank.now = VOTE_AT;
+    let score_ix = score_instruction(&env, &env.voters[0].pubkey(), &env.artworks[0], 10);
+    assert_error(
+        env.bank.send(&[score_ix], &[&env.voters[0]]).await,
+        ErrorCode::NotJudge,
+    );
+    vote_and_score(&mut env).await;
+    tally_and_finalize(&mut env).await;
+
+    // only the contest owner can sweep the vault
+    env.bank.now = CLAIM_END_AT as i64 + 1;
+    let mut sweep_ix = sweep_instruction(&env);
+    sweep_ix.accounts[0].pubkey = env.artist.pubkey();
+    assert!(env.bank.send(&[sweep_ix], &[&env.artist]).await.is_err());
+}
+
+#[tokio::test]
+async fn rejects_mismatched_artworks() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+    review_entries(&mut env).await;
+
+    // a second contest of the same owner
+    let mut args = launch_args(&env);
+    args.curation_enabled = false;
+    let launch_ix = instructions::launch(
+        &env.owner.pubkey(),
+        1,
+        1,
+        &env.prize_mint,
+        &env.owner_prize_account,
+        args,
+    );
+    env.bank.send(&[launch_ix], &[&env.owner]).await.unwrap();
+    let other_contest = pda::find_contest_address(&env.owner.pubkey(), 1).0;
+
+    // an artwork can only be voted for in its own contest
+    env.bank.now = VOTE_AT;
+    let vote_ix = instructions::vote(
+        &env.voters[0].pubkey(),
+        &other_contest,
+        &env.artworks[0],
+        0,
+        &env.prize_mint,
+        &env.voter_prize_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[vote_ix], &[&env.voters[0]]).await,
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+    // the voted artwork id must be the id of the artwork account
+    let vote_ix = instructions::vote(
+        &env.voters[0].pubkey(),
+        &env.contest,
+        &env.artworks[0],
+        1,
+        &env.prize_mint,
+        &env.voter_prize_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[vote_ix], &[&env.voters[0]]).await,
+        ErrorCode::VotedArtworkMismatch,
+    );
+    vote_and_score(&mut env).await;
+
+    // artworks are tallied in order of id
+    env.bank.now = TALLY_AT;
+    let tally_ix = instructions::tally_votes(&env.contest, &[env.artworks[1]]);
+    assert_error(
+        env.bank.send(&[tally_ix], &[]).await,
+        ErrorCode::ArtworkOutOfOrder,
+    );
+    tally_and_finalize(&mut env).await;
+
+    // the rejected artwork did not win
+    let claim_ix = claim_by_artist_instruction(&env, 1);
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.artist]).await,
+        ErrorCode::NotWinningArtwork,
+    );
+
+    // voters claim with the artwork they voted for
+    let claim_ix = instructions::claim_by_voter(
+        &env.voters[0].pubkey(),
+        &env.contest,
+        &env.artworks[1],
+        &env.prize_mint,
+        &env.voter_prize_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.voters[0]]).await,
+        ErrorCode::VotedArtworkMismatch,
+    );
+}
+
+#[tokio::test]
+async fn rejects_double_votes_and_double_claims() {
+    let mut env = setup().await;
+    initialize_and_launch(&mut env).await;
+    submit_entries(&mut env).await;
+    review_entries(&mut env).await;
+    vote_and_score(&mut env).await;
+
+    // the vote data and judge score accounts already exist
+    let vote_ix = vote_instruction(&env, 0, 0);
+    assert!(env.bank.send(&[vote_ix], &[&env.voters[0]]).await.is_err());
+    let score_ix = score_instruction(&env, &env.judge.pubkey(), &env.artworks[0], 10);
+    assert!(env.bank.send(&[score_ix], &[&env.judge]).await.is_err());
+    let artwork: Artwork = env.bank.account(&env.artworks[0]).await;
+    assert_eq!(artwork.vote_count, 2);
+    assert_eq!(artwork.judge_score_total, 8);
+
+    tally_and_finalize(&mut env).await;
+    let claim_ix = claim_by_artist_instruction(&env, 0);
+    env.bank.send(&[claim_ix], &[&env.artist]).await.unwrap();
+    let claim_ix = claim_by_artist_instruction(&env, 0);
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.artist]).await,
+        ErrorCode::NothingToClaim,
+    );
+
+    let claim_ix = claim_by_voter_instruction(&env, 0);
+    env.bank.send(&[claim_ix], &[&env.voters[0]]).await.unwrap();
+    let claim_ix = claim_by_voter_instruction(&env, 0);
+    assert_error(
+        env.bank.send(&[claim_ix], &[&env.voters[0]]).await,
+        ErrorCode::RewardAlreadyClaimed,
+    );
+
+    // the crank skips votes that were already paid
+    let distribute_ix = instructions::distribute(
+        &env.contest,
+        &env.prize_mint,
+        &[(env.voters[0].pubkey(), env.voter_prize_accounts[0])],
+    );
+    env.bank.send(&[distribute_ix], &[]).await.unwrap();
+    assert_eq!(
+        env.bank.token_balance(&env.voter_prize_accounts[0]).await,
+        300 + 150_000
+    );
+}
+
+#[tokio::test]
+async fn locks_vote_weight_until_voting_ends() {
+    let mut env = setup().await;
+    let program_owner = env.bank.context.payer.pubkey();
+    env.bank
+        .send(&[instructions::initialize(&program_owner)], &[])
+        .await
+        .unwrap();
+    let args = LaunchArgs {
+        curation_enabled: false,
+        voter_reward_mode: VoterRewardMode::VoteWeighted,
+        ..launch_args(&env)
+    };
+    let launch_ix = instructions::launch(
+        &env.owner.pubkey(),
+        0,
+        0,
+        &env.prize_mint,
+        &env.owner_prize_account,
+        args,
+    );
+    env.bank.send(&[launch_ix], &[&env.owner]).await.unwrap();
+    submit_entries(&mut env).await;
+
+    // lamports sent to the escrow address beforehand don't keep the voter from voting
+    let escrow = pda::find_vote_escrow_address(&env.contest, &env.voters[0].pubkey()).0;
+    let fund_ix =
+        system_instruction::transfer(&env.bank.context.payer.pubkey(), &escrow, 1_000_000);
+    env.bank.send(&[fund_ix], &[]).await.unwrap();
+
+    // the balance weighing the vote is moved to an escrow
+    env.bank.now = VOTE_AT;
+    let vote_ix = vote_instruction(&env, 0, 0);
+    env.bank.send(&[vote_ix], &[&env.voters[0]]).await.unwrap();
+    assert_eq!(env.bank.token_balance(&escrow).await, 300);
+    assert_eq!(
+        env.bank.token_balance(&env.voter_prize_accounts[0]).await,
+        0
+    );
+
+    // so it can not be handed over to another voter and counted again
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &env.voter_prize_accounts[0],
+        &env.voter_prize_accounts[1],
+        &env.voters[0].pubkey(),
+        &[],
+        300,
+    )
+    .unwrap();
+    assert!(env
+        .bank
+        .send(&[transfer_ix], &[&env.voters[0]])
+        .await
+        .is_err());
+    let vote_ix = vote_instruction(&env, 1, 0);
+    env.bank.send(&[vote_ix], &[&env.voters[1]]).await.unwrap();
+    let vote: VoteData = env
+        .bank
+        .account(&pda::find_vote_data_address(&env.contest, &env.voters[1].pubkey()).0)
+        .await;
+    assert_eq!(vote.weight, 100);
+    let artwork: Artwork = env.bank.account(&env.artworks[0]).await;
+    assert_eq!(artwork.vote_weight_total, 400);
+
+    // the tokens come back once voting has ended
+    let unlock_ix = instructions::unlock_vote(
+        &env.voters[0].pubkey(),
+        &env.contest,
+        &env.voter_prize_accounts[0],
+    );
+    assert_error(
+        env.bank.send(&[unlock_ix.clone()], &[&env.voters[0]]).await,
+        ErrorCode::VotingNotEnded,
+    );
+    env.bank.now = TALLY_AT;
+    env.bank
+        .send(&[unlock_ix], &[&env.voters[0]])
+        .await
+        .unwrap();
+    assert_eq!(
+        env.bank.token_balance(&env.voter_prize_accounts[0]).await,
+        300
+    );
+    assert!(!env.bank.exists(&escrow).await);
+}
+
+#[tokio::test]
+async fn migrates_legacy_accounts_once() {
+    let mut program_test = program_test();
+    let admin = add_user(&mut program_test);
+    let voter = add_user(&mut program_test);
+    add_program_data(&mut program_test, &admin.pubkey());
+    let contest_owner = add_user(&mut program_test);
+    let owner = contest_owner.pubkey();
+    let winning_artist = add_user(&mut program_test);
+    let artists = [Pubkey::new_unique(), winning_artist.pubkey()];
+    let late_voter = add_user(&mut program_test);
+    let prize_mint = add_mint(&mut program_test, None, 0, PRIZE_AMOUNT);
+    let artist_prize_account = add_token_account(&mut program_test, &prize_mint, &artists[1], 0);
+    let voter_prize_account = add_token_account(&mut program_test, &prize_mint, &voter.pubkey(), 0);
+    let late_voter_prize_account =
+        add_token_account(&mut program_test, &prize_mint, &late_voter.pubkey(), 0);
+
+    // accounts of the first release, sized 8 + size_of of its #[repr(C)] structs
+    let counter = pda::find_counter_address().0;
+    add_legacy_account::<Counter>(
+        &mut program_test,
+        counter,
+        &legacy::CounterV0 {
+            is_initialized: true,
+            contest_count: 3,
+        },
+        8 + 16,
+    );
+    let contest_id = 2;
+    let contest = pda::find_legacy_contest_address(&owner, contest_id).0;
+    let legacy_contest = legacy::ContestV0 {
+        is_initialized: true,
+        contest_id,
+        contest_owner: owner,
+        prize_amount: PRIZE_AMOUNT,
+        percentage_to_artist: PERCENTAGE_TO_ARTIST,
+        submit_start_at: T0 as u64 - 300,
+        submit_end_at: T0 as u64 - 200,
+        vote_start_at: T0 as u64 - 100,
+        vote_end_at: T0 as u64 + 100,
+        title_of_contest: b"Legacy contest".to_vec(),
+        link_to_project: b"https://example.com".to_vec(),
+        artwork_count: 2,
+        artworks_vote_counter: vec![1, 3],
+    };
+    // launch added vec_size bytes for the strings and the vote counter
+    add_legacy_account::<Contest>(&mut program_test, contest, &legacy_contest, 8 + 176 + 64);
+    let prize_vault = pda::find_legacy_prize_vault_address(&owner, contest_id).0;
+    add_packed_at(
+        &mut program_test,
+        prize_vault,
+        spl_token::state::Account {
+            mint: prize_mint,
+            owner: pda::find_prize_vault_authority_address(&contest).0,
+            amount: PRIZE_AMOUNT,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        },
+    );
+    let artworks = artists.map(|artist| pda::find_legacy_artwork_address(&contest, &artist).0);
+    for (artwork_id, artist) in artists.iter().enumerate() {
+        let legacy_artwork = legacy::ArtworkV0 {
+            is_initialized: true,
+            artwork_id: artwork_id as u64,
+            associated_contest_id: contest_id,
+            artist_key: *artist,
+            artwork_token_account: Pubkey::new_unique(),
+        };
+        add_legacy_account::<Artwork>(
+            &mut program_test,
+            artworks[artwork_id],
+            &legacy_artwork,
+            8 + 88,
+        );
+    }
+    let (artwork, artist) = (artworks[1], artists[1]);
+    let nft_mint = add_mint(&mut program_test, None, 0, 1);
+    add_packed_at(
+        &mut program_test,
+        pda::find_legacy_nft_vault_address(&contest, &artist).0,
+        spl_token::state::Account {
+            mint: nft_mint,
+            owner: pda::find_nft_vault_authority_address(&contest, &artist).0,
+            amount: 1,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        },
+    );
+    let owner_nft_account = add_token_account(&mut program_test, &nft_mint, &owner, 0);
+    let vote_data = pda::find_vote_data_address(&contest, &voter.pubkey()).0;
+    let legacy_vote = legacy::VoteDataV0 {
+        is_initialized: true,
+        voter_key: voter.pubkey(),
+        voted_artwork_id: 1,
+    };
+    add_legacy_account::<VoteData>(&mut program_test, vote_data, &legacy_vote, 8 + 48);
+    let mut bank = Bank::start(program_test, T0).await;
+
+    // only the upgrade authority of the program can migrate
+    assert_error(
+        bank.send(&[instructions::migrate_counter(&voter.pubkey())], &[&voter])
+            .await,
+        ErrorCode::NotProgramAdmin,
+    );
+    // the vote data address is checked against the contest given for it
+    assert_error(
+        bank.send(
+            &[instructions::migrate_vote_data(
+                &admin.pubkey(),
+                &counter,
+                &vote_data,
+            )],
+            &[&admin],
+        )
+        .await,
+        ErrorCode::InvalidLegacyAccount,
+    );
+
+    let migrate_ixs = [
+        instructions::migrate_counter(&admin.pubkey()),
+        instructions::migrate_vote_data(&admin.pubkey(), &contest, &vote_data),
+        instructions::migrate_artwork(&admin.pubkey(), &contest, &artwork),
+    ];
+    bank.send(&migrate_ixs, &[&admin]).await.unwrap();
+    // the contest holds the votes of the artwork not migrated yet
+    for migrated_artworks in [&artworks[..], &[artwork]] {
+        assert_error(
+            bank.send(
+                &[instructions::migrate_contest(
+                    &admin.pubkey(),
+                    &owner,
+                    contest_id,
+                    migrated_artworks,
+                )],
+                &[&admin],
+            )
+            .await,
+            ErrorCode::ArtworksNotMigrated,
+        );
+    }
+    let migrate_ixs = [
+        instructions::migrate_artwork(&admin.pubkey(), &contest, &artworks[0]),
+        instructions::migrate_contest(&admin.pubkey(), &owner, contest_id, &artworks),
+    ];
+    bank.send(&migrate_ixs, &[&admin]).await.unwrap();
+
+    let counter_account: Counter = bank.account(&counter).await;
+    assert_eq!(counter_account.contest_count, 3);
+    assert_eq!(counter_account.bump, pda::find_counter_address().1);
+    assert_eq!(counter_account.version, Counter::VERSION);
+
+    let vote: VoteData = bank.account(&vote_data).await;
+    assert_eq!(vote.contest, contest);
+    assert_eq!(vote.voter_key, voter.pubkey());
+    assert_eq!(vote.voted_artwork_id, 1);
+    assert_eq!(vote.weight, 1);
+    assert!(!vote.reward_claimed);
+    assert_eq!(
+        vote.bump,
+        pda::find_vote_data_address(&contest, &voter.pubkey()).1
+    );
+    assert_eq!(vote.version, VoteData::VERSION);
+    assert_eq!(vote.reserved, [0; RESERVED_SPACE]);
+
+    // votes were counted in the legacy contest
+    let artwork_account: Artwork = bank.account(&artwork).await;
+    assert_eq!(artwork_account.artwork_id, 1);
+    assert_eq!(artwork_account.artist_key, artist);
+    assert_eq!(artwork_account.vote_count, 3);
+    assert_eq!(artwork_account.vote_weight_total, 3);
+    assert_eq!(artwork_account.status, ArtworkStatus::Approved);
+    assert_eq!(
+        artwork_account.nft_vault_bump,
+        pda::find_legacy_nft_vault_address(&contest, &artist).1
+    );
+    assert_eq!(artwork_account.version, Artwork::VERSION);
+    let artwork_account: Artwork = bank.account(&artworks[0]).await;
+    assert_eq!(artwork_account.vote_count, 1);
+
+    let contest_account: Contest = bank.account(&contest).await;
+    assert_eq!(contest_account.contest_id, contest_id);
+    assert_eq!(contest_account.contest_owner, owner);
+    assert_eq!(contest_account.prize_mint, prize_mint);
+    assert_eq!(contest_account.prize_amount, PRIZE_AMOUNT);
+    assert_eq!(contest_account.title_of_contest, "Legacy contest");
+    assert_eq!(contest_account.link_to_project, "https://example.com");
+    assert_eq!(contest_account.artwork_count, 2);
+    assert_eq!(
+        contest_account.voter_reward_mode,
+        VoterRewardMode::WinnersOnly
+    );
+    assert_eq!(
+        contest_account.bump,
+        pda::find_legacy_contest_address(&owner, contest_id).1
+    );
+    assert_eq!(
+        contest_account.prize_vault_bump,
+        pda::find_legacy_prize_vault_address(&owner, contest_id).1
+    );
+    assert_eq!(contest_account.status, ContestStatus::Voting);
+    assert_eq!(contest_account.version, Contest::VERSION);
+
+    // the grown accounts stay rent exempt
+    for address in [counter, contest, artworks[0], artworks[1], vote_data] {
+        let account = bank
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(Rent::default().is_exempt(account.lamports, account.data.len()));
+    }
+
+    assert_error(
+        bank.send(
+            &[instructions::migrate_vote_data(
+                &admin.pubkey(),
+                &contest,
+                &vote_data,
+            )],
+            &[&admin],
+        )
+        .await,
+        ErrorCode::AccountAlreadyMigrated,
+    );
+    assert_error(
+        bank.send(
+            &[instructions::migrate_contest(
+                &admin.pubkey(),
+                &owner,
+                contest_id,
+                &artworks,
+            )],
+            &[&admin],
+        )
+        .await,
+        ErrorCode::AccountAlreadyMigrated,
+    );
+    // artworks read their votes from the legacy contest
+    assert_error(
+        bank.send(
+            &[instructions::migrate_artwork(
+                &admin.pubkey(),
+                &contest,
+                &artwork,
+            )],
+            &[&admin],
+        )
+        .await,
+        ErrorCode::ContestAlreadyMigrated,
+    );
+
+    // the migrated contest runs on at its legacy addresses
+    let vote_ix = instructions::vote(
+        &late_voter.pubkey(),
+        &contest,
+        &artworks[0],
+        0,
+        &prize_mint,
+        &late_voter_prize_account,
+    );
+    bank.send(&[vote_ix], &[&late_voter]).await.unwrap();
+    bank.now = T0 + 101;
+    bank.send(&[instructions::tally_votes(&contest, &artworks)], &[])
+        .await
+        .unwrap();
+    bank.send(&[instructions::finalize_contest(&contest)], &[])
+        .await
+        .unwrap();
+    let contest_account: Contest = bank.account(&contest).await;
+    assert_eq!(contest_account.total_vote_count, 5);
+    assert_eq!(contest_account.winning_artwork_ids, vec![1]);
+
+    let claim_ix = instructions::with_legacy_seeds(
+        instructions::claim_by_artist(&artist, &contest, 0, &prize_mint, &artist_prize_account),
+        &owner,
+        contest_id,
+        &[artist],
+    );
+    bank.send(&[claim_ix], &[&winning_artist]).await.unwrap();
+    assert_eq!(
+        bank.token_balance(&artist_prize_account).await,
+        contest_account.artist_payout_per_winner
+    );
+    let claim_ix = instructions::with_legacy_seeds(
+        instructions::claim_by_voter(
+            &voter.pubkey(),
+            &contest,
+            &artwork,
+            &prize_mint,
+            &voter_prize_account,
+        ),
+        &owner,
+        contest_id,
+        &[],
+    );
+    bank.send(&[claim_ix], &[&voter]).await.unwrap();
+    assert_eq!(
+        bank.token_balance(&voter_prize_account).await,
+        contest_account.voter_payout_per_vote
+    );
+    let claim_ix = instructions::with_legacy_seeds(
+        instructions::claim_by_contest_owner(
+            &owner,
+            &contest,
+            &artist,
+            0,
+            &nft_mint,
+            &owner_nft_account,
+        ),
+        &owner,
+        contest_id,
+        &[artist],
+    );
+    bank.send(&[claim_ix], &[&contest_owner]).await.unwrap();
+    assert_eq!(bank.token_balance(&owner_nft_account).await, 1);
+}
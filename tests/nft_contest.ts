@@ -3,22 +3,87 @@ import { assert } from 'chai';
 import * as anchor from '@project-serum/anchor';
 import { BN } from '@project-serum/anchor';
 import {
+  AuthorityType,
   createMint,
   getAccount,
   getOrCreateAssociatedTokenAccount,
   mintTo,
+  setAuthority,
   TOKEN_PROGRAM_ID,
 } from '@solana/spl-token';
 import {
+  Connection,
+  Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
   SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
 } from '@solana/web3.js';
 
 export const sleep = async (waitTime: number) =>
   new Promise(resolve =>
     setTimeout(resolve, waitTime));
 
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const encodeString = (value: string) => {
+  const bytes = Buffer.from(anchor.utils.bytes.utf8.encode(value));
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(bytes.length);
+  return Buffer.concat([length, bytes]);
+}
+
+export const findMetadataPda = (mint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(anchor.utils.bytes.utf8.encode("metadata")),
+    TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+    mint.toBuffer(),
+  ], TOKEN_METADATA_PROGRAM_ID)[0];
+
+// Creates Metaplex metadata (CreateMetadataAccountV2) with the artist as the only, verified creator.
+// The artist must be the mint authority and update authority.
+export const createNftMetadata = async (connection: Connection, artist: Keypair, mint: PublicKey) => {
+  const creators = Buffer.concat([
+    Buffer.from([1]), // Some
+    Buffer.from([1, 0, 0, 0]), // one creator
+    artist.publicKey.toBuffer(),
+    Buffer.from([1, 100]), // verified, share
+  ]);
+  const data = Buffer.concat([
+    Buffer.from([16]), // CreateMetadataAccountV2
+    encodeString("Demo Artwork"),
+    encodeString("DEMO"),
+    encodeString("https://arweave.net/demo-artwork"),
+    Buffer.from([0, 0]), // seller fee basis points
+    creators,
+    Buffer.from([0]), // no collection
+    Buffer.from([0]), // no uses
+    Buffer.from([1]), // is mutable
+  ]);
+  const ix = new TransactionInstruction({
+    programId: TOKEN_METADATA_PROGRAM_ID,
+    keys: [
+      { pubkey: findMetadataPda(mint), isSigner: false, isWritable: true },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: artist.publicKey, isSigner: true, isWritable: false },
+      { pubkey: artist.publicKey, isSigner: true, isWritable: true },
+      { pubkey: artist.publicKey, isSigner: true, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+  await sendAndConfirmTransaction(connection, new Transaction().add(ix), [artist]);
+}
+
+// Mints the single token of an NFT, attaches metadata and revokes the mint authority.
+export const mintNft = async (connection: Connection, artist: Keypair, mint: PublicKey, destination: PublicKey) => {
+  await mintTo(connection, artist, mint, destination, artist.publicKey, 1, [artist]);
+  await createNftMetadata(connection, artist, mint);
+  await setAuthority(connection, artist, mint, artist.publicKey, AuthorityType.MintTokens, null, [artist]);
+}
+
 describe("nft_contest", () => {
   // set provicder and program
   const provider = anchor.AnchorProvider.env();
@@ -57,10 +122,16 @@ describe("nft_contest", () => {
   let _counterBump = null;
   let contestPda = null;
   let _contestBump = null;
+  let ownerProfilePda = null;
+  let _ownerProfileBump = null;
+  let ownerContestPagePda = null;
+  let _ownerContestPageBump = null;
   let prizeVaultPda = null;
   let _prizeVaultBump = null;
   let prizeVaultAuthorityPda = null;
   let _prizeVaultAuthorityBump = null;
+  let artistEntriesPda = null;
+  let _artistEntriesBump = null;
   let artworkPda = null;
   let _artworkBump = null;
   let nftVaultPda = null;
@@ -73,13 +144,27 @@ describe("nft_contest", () => {
   // Constants
   const prizeAmount = new BN(500*10**9);
   const percentageToArtist = 70 as number;
-  const submitStartAt = new BN(Date.now() - 1000000) ;
-  const submitEndAt = new BN(Date.now() - 1000000 + 3000); // 3 seconds after from the submitStartAt
-  const voteStartAt = new BN(Date.now() - 1000000);
-  const voteEndAt = new BN(Date.now() - 1000000 + 3000); // 3 seconds after from the submitStartAt
-  const titleOfContest = Buffer.from(anchor.utils.bytes.utf8.encode("Demo Contest"));
-  const linkToProject = Buffer.from(anchor.utils.bytes.utf8.encode("www"));
-  const vecSize = 200;
+  // timestamps are unix seconds, as compared with the Clock sysvar on chain
+  const now = Math.floor(Date.now() / 1000);
+  const submitStartAt = new BN(now - 60);
+  const submitEndAt = new BN(now + 20); // submission and voting overlap in this test
+  const voteStartAt = new BN(now - 60);
+  const voteEndAt = new BN(now + 20);
+  const claimEndAt = new BN(now + 30); // the owner can sweep the prize vault after this
+  const contestMetadata = {
+    titleOfContest: "Demo Contest",
+    linkToProject: "www",
+    description: "Demo contest for NFTthon",
+    coverImageUri: "https://arweave.net/demo-cover",
+  };
+  const maxArtworks = 200;
+  const requiredCollection = null; // any collection is accepted
+  const maxEntriesPerArtist = 3;
+  const curationEnabled = false; // submitted artworks are votable right away
+  const judges = []; // winners are decided by public votes only
+  const judgeWeightPercent = 0;
+  const artistVestingDuration = new BN(0); // the artist prize is paid at once
+  const voterRewardMode = { winnersOnly: {} }; // voters of the winning artwork share the voter prize
   let voted_artwork_id = 0;
 
   // Unit test
@@ -148,14 +233,23 @@ describe("nft_contest", () => {
        [contestPda, _contestBump] = await PublicKey.findProgramAddress(
         [Buffer.from("contest"), 
         contestOwner.publicKey.toBuffer(),
-        Buffer.from(anchor.utils.bytes.utf8.encode((contestCount.toNumber()).toString()))
+        contestCount.toArrayLike(Buffer, "le", 8) // ids are seeded as little-endian u64
       ], program.programId);
         [prizeVaultPda, _prizeVaultBump] = await PublicKey.findProgramAddressSync(
           [Buffer.from(anchor.utils.bytes.utf8.encode("prize_vault")),  
-          contestOwner.publicKey.toBuffer(),
-          Buffer.from(anchor.utils.bytes.utf8.encode((contestCount.toNumber()).toString()))
+          contestPda.toBuffer(),
         ], program.programId);
       console.log("prize vault pda: ", prizeVaultPda.toBase58());
+      [ownerProfilePda, _ownerProfileBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("owner_profile")),
+        contestOwner.publicKey.toBuffer(),
+      ], program.programId);
+      // the first contest of an owner goes to page 0
+      [ownerContestPagePda, _ownerContestPageBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("owner_contest_page")),
+        contestOwner.publicKey.toBuffer(),
+        new BN(0).toArrayLike(Buffer, "le", 8),
+      ], program.programId);
       contestOwnerPrizeTokenAccount = await getOrCreateAssociatedTokenAccount(
         connection,
         contestOwner,
@@ -178,23 +272,32 @@ describe("nft_contest", () => {
       console.log("intial contest owner prize token account's amount", _contestOwnerPrizeTokenAccount.amount.toString())
       assert.ok(_contestOwnerPrizeTokenAccount.amount == BigInt(prizeAmount.toNumber()))
 
-      const ix = await program.methods.launch(
+      const ix = await program.methods.launch({
         prizeAmount,
         percentageToArtist,
         submitStartAt,
         submitEndAt,
         voteStartAt,
         voteEndAt,
-        titleOfContest,
-        linkToProject,
-        vecSize,
-        )
+        metadata: contestMetadata,
+        maxArtworks,
+        requiredCollection,
+        maxEntriesPerArtist,
+        curationEnabled,
+        judges,
+        judgeWeightPercent,
+        artistVestingDuration,
+        voterRewardMode,
+        claimEndAt,
+        })
       .accounts(
         {
         // tips: Variable names in sneak in rust shall be changed to camel in test typescript
         contestOwner: contestOwner.publicKey,
         counter: counterPda,
         contest: contestPda,
+        ownerProfile: ownerProfilePda,
+        ownerContestPage: ownerContestPagePda,
         prizeMint: prizeTokenMint,
         prizeVaultAccount: prizeVaultPda,
         prizeTokenAccount: contestOwnerPrizeTokenAccount.address,
@@ -230,7 +333,12 @@ describe("nft_contest", () => {
       console.log('submit start at: ', contestAccount.submitStartAt.toNumber());
       assert.ok(contestAccount.submitStartAt.toNumber() == submitStartAt.toNumber());
 
-      console.log('title of contest: ', contestAccount.titleOfContest.toString());
+      console.log('title of contest: ', contestAccount.titleOfContest);
+      assert.ok(contestAccount.titleOfContest == contestMetadata.titleOfContest);
+      assert.ok(contestAccount.linkToProject == contestMetadata.linkToProject);
+      assert.ok(contestAccount.description == contestMetadata.description);
+      assert.ok(contestAccount.coverImageUri == contestMetadata.coverImageUri);
+      assert.ok(contestAccount.requiredCollection == null);
       
       let __campaignOwnerPrizeTokenAccount = await getAccount(connection, contestOwnerPrizeTokenAccount.address);
       console.log("after-launch contest owner prize token account's amount", __campaignOwnerPrizeTokenAccount.amount.toString())
@@ -245,6 +353,13 @@ describe("nft_contest", () => {
         contestPda.toBuffer(),
       ], program.programId);
       assert.ok(_prizeVaultAccount.owner.toBase58() == prizeVaultAuthorityPda.toBase58());
+      const ownerProfileAccount = await program.account.ownerProfile.fetch(ownerProfilePda);
+      assert.ok(ownerProfileAccount.contestCount.toNumber() == 1);
+      const ownerContestPageAccount = await program.account.ownerContestPage.fetch(ownerContestPagePda);
+      assert.ok(ownerContestPageAccount.contestKeys[0].toBase58() == contestPda.toBase58());
+      assert.ok(contestAccount.bump == _contestBump);
+      assert.ok(contestAccount.prizeVaultBump == _prizeVaultBump);
+      assert.ok(contestAccount.prizeVaultAuthorityBump == _prizeVaultAuthorityBump);
       
     } catch (error) {
       console.log(error)
@@ -290,15 +405,23 @@ describe("nft_contest", () => {
       );
 
     try {
+      [artistEntriesPda, _artistEntriesBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("artist_entries")),
+        contestPda.toBuffer(),
+        artist1.publicKey.toBuffer()
+      ], program.programId);
+      // first entry of artist1, the entry index is a single byte
       [artworkPda, _artworkBump] = PublicKey.findProgramAddressSync(
         [Buffer.from(anchor.utils.bytes.utf8.encode("artwork")),  
         contestPda.toBuffer(), 
-        artist1.publicKey.toBuffer()
+        artist1.publicKey.toBuffer(),
+        Buffer.from([0]),
       ], program.programId);
       [nftVaultPda, _nftVaultBump] = PublicKey.findProgramAddressSync(
         [Buffer.from(anchor.utils.bytes.utf8.encode("nft_vault")),
         contestPda.toBuffer(),
-        artist1.publicKey.toBuffer()
+        artist1.publicKey.toBuffer(),
+        Buffer.from([0]),
         ], program.programId);
       console.log("nft vault pda: ", nftVaultPda.toBase58());
       artist1NftAccount = await getOrCreateAssociatedTokenAccount(
@@ -314,15 +437,7 @@ describe("nft_contest", () => {
         artist1.publicKey
         );
 
-      await mintTo(
-        connection,
-        artist1,
-        nftMint,
-        artist1NftAccount.address,
-        artist1.publicKey,
-        1,
-        [artist1],
-      );
+      await mintNft(connection, artist1, nftMint, artist1NftAccount.address);
 
       let _artist1NftAccount = await getAccount(connection, artist1NftAccount.address);
       console.log("intial artist1 nft account's amount", _artist1NftAccount.amount.toString())
@@ -334,8 +449,10 @@ describe("nft_contest", () => {
         // tips: Variable names in sneak in rust shall be changed to camel in test typescript
         artist: artist1.publicKey,
         contest: contestPda,
+        artistEntries: artistEntriesPda,
         artwork: artworkPda,
         nftMint: nftMint,
+        nftMetadata: findMetadataPda(nftMint),
         nftVaultAccount: nftVaultPda,
         artworkTokenAccount: artist1NftAccount.address,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -356,6 +473,11 @@ describe("nft_contest", () => {
       console.log('associated contest id: ', artworkAccount.associatedContestId.toNumber());
       assert.ok(artworkAccount.associatedContestId.toNumber() == 0);
 
+      assert.ok(artworkAccount.entryIndex == 0);
+      assert.ok(artworkAccount.status.approved !== undefined);
+      const artistEntriesAccount = await program.account.artistEntries.fetch(artistEntriesPda);
+      assert.ok(artistEntriesAccount.entryCount == 1);
+
       console.log('artist key: ', artworkAccount.artistKey.toBase58());
       assert.ok(artworkAccount.artistKey.toBase58() == artist1.publicKey.toBase58());
 
@@ -375,15 +497,22 @@ describe("nft_contest", () => {
       assert.ok(_nftVaultAccount.owner.toBase58() == nftVaultAuthorityPda.toBase58());
       
       // for artist2
+      const [artistEntriesPda2, _artistEntriesBump2] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("artist_entries")),
+        contestPda.toBuffer(),
+        artist2.publicKey.toBuffer()
+      ], program.programId);
       const [artworkPda2, _artworkVaultBump2] = PublicKey.findProgramAddressSync(
         [Buffer.from(anchor.utils.bytes.utf8.encode("artwork")),  
         contestPda.toBuffer(),
         artist2.publicKey.toBuffer(),
+        Buffer.from([0]),
       ], program.programId);
       const [nftVaultPda2, _nftVaultBump2] = PublicKey.findProgramAddressSync(
         [Buffer.from(anchor.utils.bytes.utf8.encode("nft_vault")),
         contestPda.toBuffer(),  
-        artist2.publicKey.toBuffer()
+        artist2.publicKey.toBuffer(),
+        Buffer.from([0]),
       ], program.programId);
       console.log("nft vault pda: ", nftVaultPda2.toBase58());
     
@@ -399,15 +528,7 @@ describe("nft_contest", () => {
         artist2.publicKey
         );
 
-        await mintTo(
-          connection,
-          artist2,
-          nftMint2,
-          artist2NftAccount.address,
-          artist2.publicKey,
-          1,
-          [artist2],
-        );
+      await mintNft(connection, artist2, nftMint2, artist2NftAccount.address);
 
       let _artist2NftAccount = await getAccount(connection, artist2NftAccount.address);
       console.log("intial artist2 nft account's amount", _artist2NftAccount.amount.toString())
@@ -418,8 +539,10 @@ describe("nft_contest", () => {
         {
         artist: artist2.publicKey,
         contest: contestPda,
+        artistEntries: artistEntriesPda2,
         artwork: artworkPda2,
         nftMint: nftMint2,
+        nftMetadata: findMetadataPda(nftMint2),
         nftVaultAccount: nftVaultPda2,
         artworkTokenAccount: artist2NftAccount.address,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -465,13 +588,22 @@ describe("nft_contest", () => {
         voter1.publicKey
         );
       
-      const ix = await program.methods.vote(voted_artwork_id)
+      const ix = await program.methods.vote(new BN(voted_artwork_id))
       .accounts(
         {
         voter: voter1.publicKey,
         artwork: artworkPda,
         contest: contestPda,
         voteData: voteDataPda,
+        prizeMint: prizeTokenMint,
+        voterTokenAccount: voter1TokenAccount.address,
+        voteEscrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("vote_escrow")),
+          contestPda.toBuffer(),
+          voter1.publicKey.toBuffer(),
+        ], program.programId)[0],
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([voter1])
@@ -499,30 +631,91 @@ describe("nft_contest", () => {
         voter2.publicKey
         );
       
-      const ix2 = await program.methods.vote(voted_artwork_id)
+      const ix2 = await program.methods.vote(new BN(voted_artwork_id))
       .accounts(
         {
         voter: voter2.publicKey,
         artwork: artworkPda,
         contest: contestPda,
         voteData: voteDataPda2,
+        prizeMint: prizeTokenMint,
+        voterTokenAccount: voter2TokenAccount.address,
+        voteEscrow: PublicKey.findProgramAddressSync(
+          [Buffer.from(anchor.utils.bytes.utf8.encode("vote_escrow")),
+          contestPda.toBuffer(),
+          voter2.publicKey.toBuffer(),
+        ], program.programId)[0],
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([voter2])
       .rpc();
 
       // assertion
-      const contestAccount = await program.account.contest.fetch(contestPda);
-      console.log('artwork vote counter | artwork1:', 
-          contestAccount.artworksVoteCounter[0].toNumber(),
-          'artwork2:', 
-          contestAccount.artworksVoteCounter[1].toNumber());
+      const artworkAccount = await program.account.artwork.fetch(artworkPda);
+      console.log('artwork1 vote count:', artworkAccount.voteCount.toNumber());
+      assert.ok(artworkAccount.voteCount.toNumber() == 2);
     
     } catch (error) {
       console.log(error)
     }
   })
 
+  it("TallyVotes", async () => {
+    // wait until voting has ended
+    await sleep((voteEndAt.toNumber() + 2) * 1000 - Date.now());
+
+    try {
+      const [artworkPda2, _artworkBump2] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("artwork")),
+        contestPda.toBuffer(),
+        artist2.publicKey.toBuffer(),
+        Buffer.from([0]),
+      ], program.programId);
+
+      const ix = await program.methods.tallyVotes()
+      .accounts({
+        contest: contestPda,
+      })
+      .remainingAccounts([
+        { pubkey: artworkPda, isWritable: false, isSigner: false },
+        { pubkey: artworkPda2, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+      // assertion
+      const contestAccount = await program.account.contest.fetch(contestPda);
+      console.log('tallied artworks: ', contestAccount.talliedArtworkCount.toNumber());
+      assert.ok(contestAccount.talliedArtworkCount.toNumber() == 2);
+      assert.ok(contestAccount.totalVoteCount.toNumber() == 2);
+      assert.ok(contestAccount.winningVoteTotal.toNumber() == 2);
+    } catch (error) {
+      console.log(error)
+    }
+  })
+
+  it("FinalizeContest", async () => {
+    try {
+      const ix = await program.methods.finalizeContest()
+      .accounts({
+        contest: contestPda,
+      })
+      .rpc();
+
+      // assertion
+      const contestAccount = await program.account.contest.fetch(contestPda);
+      assert.ok(contestAccount.isFinalized == true);
+      console.log('winning artwork ids: ', contestAccount.winningArtworkIds.map((id) => id.toNumber()));
+      assert.ok(contestAccount.winningArtworkIds.length == 1);
+      assert.ok(contestAccount.winningArtworkIds[0].toNumber() == 0);
+      console.log('artist payout per winner: ', contestAccount.artistPayoutPerWinner.toString());
+      console.log('voter payout per vote: ', contestAccount.voterPayoutPerVote.toString());
+    } catch (error) {
+      console.log(error)
+    }
+  })
+
   it("ClaimByArtist", async () => {
     try {
       const ix = await program.methods.claimByArtist()
@@ -549,6 +742,10 @@ describe("nft_contest", () => {
 
       let _artistTokenAccount = await getAccount(connection, artist1PrizeTokenAccount.address);
       console.log("artist token account's amount", _artistTokenAccount.amount.toString())
+      const contestAccount = await program.account.contest.fetch(contestPda);
+      assert.ok(_artistTokenAccount.amount == BigInt(contestAccount.artistPayoutPerWinner.toString()));
+      const artworkAccount = await program.account.artwork.fetch(artworkPda);
+      assert.ok(artworkAccount.prizeReleasedAmount.eq(contestAccount.artistPayoutPerWinner));
       // assert.ok(_artistTokenAccount.amount.toNumber() == prizeAmount.toNumber() * percentageToArtist / 100);
     
       // for vote2 which will fail
@@ -610,14 +807,58 @@ describe("nft_contest", () => {
       .rpc();
 
       const contestAccount = await program.account.contest.fetch(contestPda);
-      const winnerNumVote = contestAccount.artworksVoteCounter[0].toNumber();
+      const voterPayoutPerVote = contestAccount.voterPayoutPerVote;
 
       let _prizeVaultAccount = await getAccount(connection, prizeVaultPda);
       console.log("prize vault token account's amount", _prizeVaultAccount.amount.toString())
 
       let _voterTokenAccount = await getAccount(connection, voter1TokenAccount.address);
       console.log("voter token account's amount", _voterTokenAccount.amount.toString())
-      // assert.ok(_voterTokenAccount.amount == prizeAmount.toNumber() * (100 - percentageToArtist) / winnerNumVote / 100);
+      assert.ok(_voterTokenAccount.amount == BigInt(voterPayoutPerVote.toString()));
+
+      const voteDataAccount = await program.account.voteData.fetch(voteDataPda);
+      assert.ok(voteDataAccount.rewardClaimed == true);
+    } catch (error) {
+      console.log(error)
+    }
+  }
+  )
+
+  it("Distribute", async () => {
+    try {
+      const [voteDataPda2, _voteDataBump2] = PublicKey.findProgramAddressSync(
+        [Buffer.from(anchor.utils.bytes.utf8.encode("vote")),
+        contestPda.toBuffer(),
+        voter2.publicKey.toBuffer()
+      ], program.programId);
+
+      // voter1 already claimed and is skipped, voter2 is paid by the crank
+      const ix = await program.methods.distribute()
+      .accounts(
+        {
+        contest: contestPda,
+        prizeMint: prizeTokenMint,
+        prizeVaultAccount: prizeVaultPda,
+        prizeVaultAuthority: prizeVaultAuthorityPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: voteDataPda, isWritable: true, isSigner: false },
+        { pubkey: voter1TokenAccount.address, isWritable: true, isSigner: false },
+        { pubkey: voteDataPda2, isWritable: true, isSigner: false },
+        { pubkey: voter2TokenAccount.address, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+      // assertion
+      const contestAccount = await program.account.contest.fetch(contestPda);
+      const voterPayoutPerVote = contestAccount.voterPayoutPerVote;
+      let _voter1TokenAccount = await getAccount(connection, voter1TokenAccount.address);
+      assert.ok(_voter1TokenAccount.amount == BigInt(voterPayoutPerVote.toString()));
+      let _voter2TokenAccount = await getAccount(connection, voter2TokenAccount.address);
+      assert.ok(_voter2TokenAccount.amount == BigInt(voterPayoutPerVote.toString()));
+      const voteDataAccount2 = await program.account.voteData.fetch(voteDataPda2);
+      assert.ok(voteDataAccount2.rewardClaimed == true);
     } catch (error) {
       console.log(error)
     }
@@ -664,4 +905,38 @@ describe("nft_contest", () => {
     }
   }
   )
-})
\ No newline at end of file
+
+  it("SweepRemainder", async () => {
+    // wait for the claim period to end
+    await sleep((claimEndAt.toNumber() + 2) * 1000 - Date.now());
+    try {
+      let _prizeVaultAccount = await getAccount(connection, prizeVaultPda);
+      const remainder = _prizeVaultAccount.amount;
+      let _ownerPrizeTokenAccount = await getAccount(connection, contestOwnerPrizeTokenAccount.address);
+      const ownerAmountBefore = _ownerPrizeTokenAccount.amount;
+
+      const ix = await program.methods.sweepRemainder()
+      .accounts(
+        {
+        contestOwner: contestOwner.publicKey,
+        contest: contestPda,
+        prizeMint: prizeTokenMint,
+        prizeVaultAccount: prizeVaultPda,
+        prizeVaultAuthority: prizeVaultAuthorityPda,
+        destinationTokenAccount: contestOwnerPrizeTokenAccount.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([contestOwner])
+      .rpc();
+
+      // assertion
+      _ownerPrizeTokenAccount = await getAccount(connection, contestOwnerPrizeTokenAccount.address);
+      assert.ok(_ownerPrizeTokenAccount.amount == ownerAmountBefore + remainder);
+      const vaultInfo = await connection.getAccountInfo(prizeVaultPda);
+      assert.ok(vaultInfo == null);
+    } catch (error) {
+      console.log(error)
+    }
+  }
+  )
+})